@@ -0,0 +1,193 @@
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::node_dist;
+use crate::settings::SETTINGS_FILE;
+
+// The frontend's persisted state, written through write_config_file
+pub const DATA_FILE: &str = "data.json";
+
+// A file of this name next to the executable keeps the configuration there too,
+// for copies run from a USB stick or an unpacked archive
+const PORTABLE_MARKER: &str = "portable";
+
+static CONFIG_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+fn exe_dir() -> Result<PathBuf, String> {
+    let mut path = std::env::current_exe().map_err(|e| e.to_string())?;
+    path.pop();
+    Ok(path)
+}
+
+// Chooses the directory all configuration lives in, once at startup: the app config
+// directory, or the exe directory in portable mode
+pub fn init(app_config_dir: PathBuf) -> Result<PathBuf, String> {
+    let exe_dir = exe_dir()?;
+    let dir = if exe_dir.join(PORTABLE_MARKER).exists() {
+        exe_dir
+    } else {
+        fs::create_dir_all(&app_config_dir).map_err(|e| e.to_string())?;
+        migrate(&exe_dir, &app_config_dir, DATA_FILE);
+        app_config_dir
+    };
+    // Backend settings used to sit beside the managed Node versions
+    if let Some(root) = node_dist::managed_root() {
+        migrate(&root, &dir, SETTINGS_FILE);
+    }
+    Ok(CONFIG_DIR.get_or_init(|| dir).clone())
+}
+
+// Earlier versions kept data.json next to the executable and settings.json in the data
+// directory. A file is copied over unless the new location already has one, and removed
+// afterwards where the old directory is writable
+fn migrate(from: &Path, to: &Path, name: &str) {
+    let old = from.join(name);
+    let new = to.join(name);
+    if !old.is_file() || new.exists() {
+        return;
+    }
+    match fs::copy(&old, &new) {
+        Ok(_) => {
+            println!("Moved {} to {}", old.display(), new.display());
+            let _ = fs::remove_file(&old);
+        }
+        Err(e) => eprintln!("Failed to migrate {}: {}", old.display(), e),
+    }
+}
+
+pub fn config_dir() -> Result<PathBuf, String> {
+    CONFIG_DIR.get().cloned().ok_or_else(|| "The configuration directory is not set up yet".to_string())
+}
+
+// The only files the frontend may read or write through read_config_file and write_config_file
+const ALLOWED_FILES: &[&str] = &[DATA_FILE];
+
+// Names come straight from the webview, so anything that could leave the config
+// directory is refused before the allowlist is even consulted
+fn validate_name(filename: &str) -> Result<(), String> {
+    if filename.is_empty() {
+        return Err("Config file name is empty".to_string());
+    }
+    // Checked by hand as well, so a Windows path is recognised on every platform
+    if Path::new(filename).is_absolute() || filename.starts_with(['/', '\\']) || filename.contains(':') {
+        return Err(format!("Absolute paths are not allowed: {}", filename));
+    }
+    if filename.contains(['/', '\\']) {
+        return Err(format!("Path separators are not allowed: {}", filename));
+    }
+    if filename == ".." || filename.contains("..") {
+        return Err(format!("Parent directory references are not allowed: {}", filename));
+    }
+    if !ALLOWED_FILES.contains(&filename) {
+        return Err(format!("Unknown config file: {}", filename));
+    }
+    Ok(())
+}
+
+// Resolves an allowlisted config file name inside the config directory
+pub fn config_path(filename: &str) -> Result<PathBuf, String> {
+    validate_name(filename)?;
+    Ok(config_dir()?.join(filename))
+}
+
+// The parts of a saved project the backend cares about
+#[derive(Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SavedProject {
+    pub name: String,
+    #[serde(default)]
+    pub node_version: String,
+}
+
+#[derive(Deserialize)]
+struct SavedData {
+    #[serde(default)]
+    projects: Vec<SavedProject>,
+}
+
+pub fn saved_projects() -> Vec<SavedProject> {
+    config_dir()
+        .ok()
+        .and_then(|dir| fs::read_to_string(dir.join(DATA_FILE)).ok())
+        .and_then(|content| serde_json::from_str::<SavedData>(&content).ok())
+        .map(|data| data.projects)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_allowlisted_names() {
+        assert!(validate_name(DATA_FILE).is_ok());
+    }
+
+    #[test]
+    fn rejects_names_outside_the_allowlist() {
+        assert!(validate_name("settings.json").unwrap_err().starts_with("Unknown config file"));
+        assert!(validate_name("").is_err());
+    }
+
+    #[test]
+    fn rejects_path_separators() {
+        for name in ["config/data.json", "config\\data.json", "./data.json", "data.json/"] {
+            assert!(validate_name(name).unwrap_err().starts_with("Path separators"), "{}", name);
+        }
+    }
+
+    #[test]
+    fn rejects_parent_directory_references() {
+        for name in ["..", "..data.json", "data.json.."] {
+            assert!(validate_name(name).unwrap_err().starts_with("Parent directory"), "{}", name);
+        }
+        assert!(validate_name("../../.bashrc").is_err());
+    }
+
+    fn temp_dirs(name: &str) -> (PathBuf, PathBuf) {
+        let root = std::env::temp_dir().join(format!("fp-config-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let (from, to) = (root.join("old"), root.join("new"));
+        fs::create_dir_all(&from).unwrap();
+        fs::create_dir_all(&to).unwrap();
+        (from, to)
+    }
+
+    #[test]
+    fn migrate_moves_the_old_file() {
+        let (from, to) = temp_dirs("move");
+        fs::write(from.join(DATA_FILE), "old").unwrap();
+        migrate(&from, &to, DATA_FILE);
+        assert_eq!(fs::read_to_string(to.join(DATA_FILE)).unwrap(), "old");
+        assert!(!from.join(DATA_FILE).exists());
+        let _ = fs::remove_dir_all(from.parent().unwrap());
+    }
+
+    #[test]
+    fn migrate_keeps_an_existing_file() {
+        let (from, to) = temp_dirs("keep");
+        fs::write(from.join(DATA_FILE), "old").unwrap();
+        fs::write(to.join(DATA_FILE), "new").unwrap();
+        migrate(&from, &to, DATA_FILE);
+        assert_eq!(fs::read_to_string(to.join(DATA_FILE)).unwrap(), "new");
+        assert!(from.join(DATA_FILE).exists());
+        let _ = fs::remove_dir_all(from.parent().unwrap());
+    }
+
+    #[test]
+    fn migrate_without_an_old_file_does_nothing() {
+        let (from, to) = temp_dirs("none");
+        migrate(&from, &to, DATA_FILE);
+        assert!(!to.join(DATA_FILE).exists());
+        let _ = fs::remove_dir_all(from.parent().unwrap());
+    }
+
+    #[test]
+    fn rejects_absolute_paths() {
+        for name in ["/etc/passwd", "C:\\Windows\\win.ini", "C:data.json", "\\\\server\\share"] {
+            assert!(validate_name(name).unwrap_err().starts_with("Absolute paths"), "{}", name);
+        }
+    }
+}
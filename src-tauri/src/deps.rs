@@ -0,0 +1,414 @@
+use tauri::command;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::project::read_package_json;
+use crate::version::{Range, Version};
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyMismatch {
+    name: String,
+    dependency_type: String,
+    declared: String,
+    installed: Option<String>,
+    locked: Option<String>,
+    // "missing", "out-of-range" or "lockfile-mismatch"
+    issue: String,
+}
+
+// Declared specs that cannot be evaluated offline (git urls, local paths, dist-tags) yield None
+fn declared_range(spec: &str) -> Option<Range> {
+    let spec = spec.trim();
+    let spec = spec.strip_prefix("workspace:").unwrap_or(spec);
+    // Aliases look like "npm:real-name@^1.2.0"
+    let spec = match spec.strip_prefix("npm:") {
+        Some(alias) => alias.rsplit_once('@').map(|(_, range)| range)?,
+        None => spec,
+    };
+    if spec.contains(':') || spec.contains('/') {
+        return None;
+    }
+    Range::parse(spec)
+}
+
+fn installed_version(project_path: &Path, name: &str) -> Option<String> {
+    let manifest = project_path.join("node_modules").join(name).join("package.json");
+    let content = fs::read_to_string(manifest).ok()?;
+    let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+    json.get("version").and_then(|v| v.as_str()).map(|v| v.to_string())
+}
+
+fn npm_locked_versions(content: &str) -> HashMap<String, String> {
+    let mut out = HashMap::new();
+    let json: serde_json::Value = match serde_json::from_str(content) {
+        Ok(json) => json,
+        Err(_) => return out,
+    };
+
+    // lockfileVersion 2/3 keys top-level installs as "node_modules/<name>"
+    if let Some(packages) = json.get("packages").and_then(|p| p.as_object()) {
+        for (key, entry) in packages {
+            if let Some(name) = key.strip_prefix("node_modules/") {
+                if name.contains("/node_modules/") {
+                    continue;
+                }
+                if let Some(version) = entry.get("version").and_then(|v| v.as_str()) {
+                    out.insert(name.to_string(), version.to_string());
+                }
+            }
+        }
+    } else if let Some(deps) = json.get("dependencies").and_then(|d| d.as_object()) {
+        for (name, entry) in deps {
+            if let Some(version) = entry.get("version").and_then(|v| v.as_str()) {
+                out.insert(name.clone(), version.to_string());
+            }
+        }
+    }
+    out
+}
+
+// Keyed by "<name>@<declared range>", as yarn resolves each requested range separately
+fn yarn_locked_versions(content: &str) -> HashMap<String, String> {
+    let mut out = HashMap::new();
+    let mut current: Vec<String> = Vec::new();
+
+    for line in content.lines() {
+        if line.starts_with('#') || line.trim().is_empty() {
+            continue;
+        }
+        if !line.starts_with(' ') {
+            current = line
+                .trim_end_matches(':')
+                .split(", ")
+                .map(|spec| spec.trim_matches('"').replace("@npm:", "@"))
+                // yarn 2+ opens with a "__metadata:" block that carries the lockfile version
+                .filter(|spec| spec != "__metadata")
+                .collect();
+            continue;
+        }
+        let text = line.trim();
+        let version = text
+            .strip_prefix("version ")
+            .or_else(|| text.strip_prefix("version: "))
+            .map(|v| v.trim().trim_matches('"'));
+        if let Some(version) = version {
+            for spec in current.drain(..) {
+                out.insert(spec, version.to_string());
+            }
+        }
+    }
+    out
+}
+
+fn pnpm_locked_versions(content: &str) -> HashMap<String, String> {
+    let clean = |v: &str| -> String {
+        let v = v.trim().trim_matches('\'').trim_matches('"');
+        // Strip peer suffixes: "3.4.21(typescript@5.3.3)" or the older "3.2.0_typescript@4.5.0"
+        v.split(['(', '_']).next().unwrap_or(v).to_string()
+    };
+
+    let mut out = HashMap::new();
+    // Since lockfile v6, the project's own dependencies live under "importers: .:"
+    let has_importers = content.lines().any(|l| l == "importers:");
+    let base = if has_importers { 4 } else { 0 };
+    let mut in_root = !has_importers;
+    let mut in_deps = false;
+    let mut current: Option<String> = None;
+
+    for line in content.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+        let text = line.trim();
+
+        if has_importers && indent < base {
+            in_root = indent == 2 && (text == ".:" || text == "'.':");
+            in_deps = false;
+            continue;
+        }
+        if !in_root {
+            continue;
+        }
+        if indent == base {
+            in_deps = matches!(text, "dependencies:" | "devDependencies:" | "optionalDependencies:");
+            current = None;
+            continue;
+        }
+        if !in_deps {
+            continue;
+        }
+
+        if indent == base + 2 {
+            let (name, rest) = match text.strip_suffix(':') {
+                Some(name) => (name, ""),
+                None => match text.split_once(": ") {
+                    Some(pair) => pair,
+                    None => continue,
+                },
+            };
+            let name = name.trim_matches('\'').trim_matches('"').to_string();
+            if rest.trim().is_empty() {
+                current = Some(name);
+            } else {
+                out.insert(name, clean(rest));
+                current = None;
+            }
+        } else if indent == base + 4 {
+            if let (Some(name), Some(version)) = (&current, text.strip_prefix("version:")) {
+                out.insert(name.clone(), clean(version));
+            }
+        }
+    }
+    out
+}
+
+#[command]
+pub fn check_dependency_versions(path: String) -> Result<Vec<DependencyMismatch>, String> {
+    let project_path = Path::new(&path);
+    let pkg = read_package_json(project_path)?;
+
+    let read = |file: &str| fs::read_to_string(project_path.join(file)).ok();
+    let (locked, yarn) = if let Some(content) = read("package-lock.json").or_else(|| read("npm-shrinkwrap.json")) {
+        (npm_locked_versions(&content), false)
+    } else if let Some(content) = read("pnpm-lock.yaml") {
+        (pnpm_locked_versions(&content), false)
+    } else if let Some(content) = read("yarn.lock") {
+        (yarn_locked_versions(&content), true)
+    } else {
+        (HashMap::new(), false)
+    };
+
+    let groups = [
+        ("dependencies", pkg.dependencies.unwrap_or_default()),
+        ("devDependencies", pkg.dev_dependencies.unwrap_or_default()),
+    ];
+
+    let mut mismatches = Vec::new();
+    for (dependency_type, deps) in groups {
+        for (name, declared) in deps {
+            let range = match declared_range(&declared) {
+                Some(range) => range,
+                None => continue,
+            };
+
+            let installed = installed_version(project_path, &name);
+            let locked_version = if yarn {
+                locked.get(&format!("{}@{}", name, declared.trim_start_matches("npm:"))).cloned()
+            } else {
+                locked.get(&name).cloned()
+            };
+
+            let issue = match installed.as_deref().map(Version::parse) {
+                None => Some("missing"),
+                Some(Some(v)) if !range.matches(&v) => Some("out-of-range"),
+                Some(v) => match (v, locked_version.as_deref().and_then(Version::parse)) {
+                    (Some(v), Some(l)) if v != l => Some("lockfile-mismatch"),
+                    _ => None,
+                },
+            };
+
+            if let Some(issue) = issue {
+                mismatches.push(DependencyMismatch {
+                    name,
+                    dependency_type: dependency_type.to_string(),
+                    declared,
+                    installed,
+                    locked: locked_version,
+                    issue: issue.to_string(),
+                });
+            }
+        }
+    }
+
+    mismatches.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(mismatches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn in_range(spec: &str, version: &str) -> bool {
+        declared_range(spec).unwrap().matches(&Version::parse(version).unwrap())
+    }
+
+    #[test]
+    fn declared_ranges() {
+        assert!(in_range("^1.2.0", "1.9.0"));
+        assert!(!in_range("^1.2.0", "2.0.0"));
+        assert!(in_range("~4.17", "4.17.21"));
+        assert!(!in_range("~4.17", "4.18.0"));
+        assert!(in_range(">=2 <3 || ^5", "5.1.0"));
+        assert!(in_range("workspace:^1.0.0", "1.4.0"));
+        assert!(in_range("npm:@scope/real@^3.1.0", "3.2.0"));
+        assert!(!in_range("npm:real@~3.1.0", "3.2.0"));
+        assert!(in_range("*", "0.0.1"));
+    }
+
+    #[test]
+    fn skips_specs_that_need_the_network_or_disk() {
+        assert!(declared_range("github:user/repo#main").is_none());
+        assert!(declared_range("git+https://example.com/repo.git").is_none());
+        assert!(declared_range("file:../local").is_none());
+        assert!(declared_range("./vendor/pkg").is_none());
+        assert!(declared_range("latest").is_none());
+    }
+
+    #[test]
+    fn npm_lockfile_v3_keeps_top_level_installs() {
+        let content = r#"{
+            "lockfileVersion": 3,
+            "packages": {
+                "": { "name": "demo" },
+                "node_modules/lodash": { "version": "4.17.21" },
+                "node_modules/@scope/pkg": { "version": "1.0.0" },
+                "node_modules/lodash/node_modules/nested": { "version": "9.9.9" }
+            }
+        }"#;
+        let locked = npm_locked_versions(content);
+        assert_eq!(locked.len(), 2);
+        assert_eq!(locked["lodash"], "4.17.21");
+        assert_eq!(locked["@scope/pkg"], "1.0.0");
+    }
+
+    #[test]
+    fn npm_lockfile_v1_and_garbage() {
+        let content = r#"{ "lockfileVersion": 1, "dependencies": { "lodash": { "version": "4.17.20" } } }"#;
+        assert_eq!(npm_locked_versions(content)["lodash"], "4.17.20");
+        assert!(npm_locked_versions("not json").is_empty());
+    }
+
+    #[test]
+    fn yarn_classic_lockfile() {
+        let content = "\
+# THIS IS AN AUTOGENERATED FILE. DO NOT EDIT THIS FILE DIRECTLY.
+# yarn lockfile v1
+
+
+lodash@^4.17.0, lodash@^4.17.21:
+  version \"4.17.21\"
+  resolved \"https://registry.yarnpkg.com/lodash/-/lodash-4.17.21.tgz\"
+
+\"@scope/pkg@~1.0.0\":
+  version \"1.0.3\"
+";
+        let locked = yarn_locked_versions(content);
+        assert_eq!(locked["lodash@^4.17.0"], "4.17.21");
+        assert_eq!(locked["lodash@^4.17.21"], "4.17.21");
+        assert_eq!(locked["@scope/pkg@~1.0.0"], "1.0.3");
+    }
+
+    #[test]
+    fn yarn_berry_lockfile() {
+        let content = "\
+__metadata:
+  version: 6
+
+\"lodash@npm:^4.17.21\":
+  version: 4.17.21
+  resolution: \"lodash@npm:4.17.21\"
+
+\"alias@npm:real@^2.0.0\":
+  version: 2.1.0
+";
+        let locked = yarn_locked_versions(content);
+        assert_eq!(locked["lodash@^4.17.21"], "4.17.21");
+        assert_eq!(locked["alias@real@^2.0.0"], "2.1.0");
+        assert!(!locked.contains_key("__metadata"));
+    }
+
+    #[test]
+    fn pnpm_lockfile_with_importers() {
+        let content = "\
+lockfileVersion: '9.0'
+
+importers:
+
+  .:
+    dependencies:
+      vue:
+        specifier: ^3.4.0
+        version: 3.4.21(typescript@5.3.3)
+    devDependencies:
+      '@vitejs/plugin-vue':
+        specifier: ^5.0.0
+        version: 5.0.4(vite@5.1.4)(vue@3.4.21)
+
+  packages/other:
+    dependencies:
+      lodash:
+        specifier: ^4.0.0
+        version: 4.17.21
+
+packages:
+
+  vue@3.4.21:
+    resolution: {integrity: sha512-abc}
+";
+        let locked = pnpm_locked_versions(content);
+        assert_eq!(locked.len(), 2);
+        assert_eq!(locked["vue"], "3.4.21");
+        assert_eq!(locked["@vitejs/plugin-vue"], "5.0.4");
+    }
+
+    #[test]
+    fn pnpm_lockfile_before_importers() {
+        let content = "\
+lockfileVersion: 5.4
+
+specifiers:
+  vue: ^3.2.0
+  vite: ^3.0.0
+
+dependencies:
+  vue: 3.2.0_typescript@4.5.0
+
+devDependencies:
+  vite: 3.0.0
+
+packages:
+
+  /vue/3.2.0_typescript@4.5.0:
+    resolution: {integrity: sha512-abc}
+";
+        let locked = pnpm_locked_versions(content);
+        assert_eq!(locked.len(), 2);
+        assert_eq!(locked["vue"], "3.2.0");
+        assert_eq!(locked["vite"], "3.0.0");
+    }
+
+    #[test]
+    fn reports_missing_out_of_range_and_lockfile_mismatches() {
+        let dir = std::env::temp_dir().join(format!("fp-deps-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("package.json"),
+            r#"{"dependencies":{"ok":"^1.0.0","old":"^2.0.0","drift":"^3.0.0","gone":"^1.0.0","git":"github:a/b"}}"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("package-lock.json"),
+            r#"{"packages":{"node_modules/ok":{"version":"1.2.0"},"node_modules/drift":{"version":"3.1.0"}}}"#,
+        )
+        .unwrap();
+        for (name, version) in [("ok", "1.2.0"), ("old", "1.9.0"), ("drift", "3.2.0")] {
+            let module = dir.join("node_modules").join(name);
+            fs::create_dir_all(&module).unwrap();
+            fs::write(module.join("package.json"), format!(r#"{{"version":"{}"}}"#, version)).unwrap();
+        }
+
+        let found: Vec<(String, String)> = check_dependency_versions(dir.to_string_lossy().into_owned())
+            .unwrap()
+            .into_iter()
+            .map(|m| (m.name, m.issue))
+            .collect();
+        let expected = [("drift", "lockfile-mismatch"), ("gone", "missing"), ("old", "out-of-range")];
+        assert_eq!(found, expected.map(|(n, i)| (n.to_string(), i.to_string())));
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,404 @@
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use crate::node_dist;
+use crate::version::{NodeSpec, Version};
+
+#[cfg(target_os = "windows")]
+const NODE_EXE: &str = "node.exe";
+#[cfg(not(target_os = "windows"))]
+const NODE_EXE: &str = "node";
+
+type Discover = fn() -> Vec<(String, PathBuf)>;
+
+// A Node installation found on disk: version ("v20.11.1"), the directory holding the
+// node binary, and the tool that installed it
+pub struct FoundNode {
+    pub version: String,
+    pub bin_dir: PathBuf,
+    pub source: &'static str,
+}
+
+// Each version manager's install layout, checked in order; the first source to claim a
+// binary wins, so managers come before the system locations that may link into them
+const SOURCES: &[(&str, Discover)] = &[
+    ("nvm", nvm),
+    ("managed", managed),
+    ("fnm", fnm),
+    ("volta", volta),
+    ("asdf", asdf),
+    ("n", n),
+    ("nvs", nvs),
+    ("system", homebrew),
+    ("system", system),
+];
+
+fn home() -> PathBuf {
+    dirs::home_dir().unwrap_or_default()
+}
+
+fn env_dir(var: &str) -> Option<PathBuf> {
+    env::var_os(var).filter(|v| !v.is_empty()).map(PathBuf::from)
+}
+
+// Node builds keep the binary in bin/, except on Windows where it sits at the root
+fn with_bin(dir: PathBuf) -> PathBuf {
+    if cfg!(target_os = "windows") {
+        dir
+    } else {
+        dir.join("bin")
+    }
+}
+
+// Scans `<dir>/<version>/<sub...>` for node binaries, accepting "v20.11.1" and "20.11.1" folders
+fn scan_versions(dir: &Path, sub: &[&str]) -> Vec<(String, PathBuf)> {
+    let mut out = Vec::new();
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let version = match Version::parse(&name) {
+                Some(v) => format!("v{}", v),
+                None => continue,
+            };
+            let bin_dir = with_bin(sub.iter().fold(entry.path(), |p, s| p.join(s)));
+            if bin_dir.join(NODE_EXE).is_file() {
+                out.push((version, bin_dir));
+            }
+        }
+    }
+    out
+}
+
+fn nvm() -> Vec<(String, PathBuf)> {
+    if cfg!(target_os = "windows") {
+        return env_dir("NVM_HOME").map(|d| scan_versions(&d, &[])).unwrap_or_default();
+    }
+    let dir = env_dir("NVM_DIR").unwrap_or_else(|| home().join(".nvm"));
+    scan_versions(&dir.join("versions").join("node"), &[])
+}
+
+fn managed() -> Vec<(String, PathBuf)> {
+    node_dist::versions_dir().map(|d| scan_versions(&d, &[])).unwrap_or_default()
+}
+
+fn fnm() -> Vec<(String, PathBuf)> {
+    let candidates = [
+        env_dir("FNM_DIR"),
+        dirs::data_dir().map(|d| d.join("fnm")),
+        Some(home().join(".local").join("share").join("fnm")),
+        Some(home().join(".fnm")),
+    ];
+    candidates
+        .into_iter()
+        .flatten()
+        .flat_map(|d| scan_versions(&d.join("node-versions"), &["installation"]))
+        .collect()
+}
+
+fn volta() -> Vec<(String, PathBuf)> {
+    let dir = env_dir("VOLTA_HOME").or_else(|| {
+        if cfg!(target_os = "windows") {
+            dirs::data_local_dir().map(|d| d.join("Volta"))
+        } else {
+            Some(home().join(".volta"))
+        }
+    });
+    dir.map(|d| scan_versions(&d.join("tools").join("image").join("node"), &[]))
+        .unwrap_or_default()
+}
+
+fn asdf() -> Vec<(String, PathBuf)> {
+    let dir = env_dir("ASDF_DATA_DIR").unwrap_or_else(|| home().join(".asdf"));
+    scan_versions(&dir.join("installs").join("nodejs"), &[])
+}
+
+fn n() -> Vec<(String, PathBuf)> {
+    let prefix = env_dir("N_PREFIX").unwrap_or_else(|| PathBuf::from("/usr/local"));
+    scan_versions(&prefix.join("n").join("versions").join("node"), &[])
+}
+
+fn nvs() -> Vec<(String, PathBuf)> {
+    let dir = env_dir("NVS_HOME").or_else(|| {
+        if cfg!(target_os = "windows") {
+            dirs::data_local_dir().map(|d| d.join("nvs"))
+        } else {
+            Some(home().join(".nvs"))
+        }
+    });
+    let arch = match env::consts::ARCH {
+        "aarch64" => "arm64",
+        "x86" => "x86",
+        _ => "x64",
+    };
+    dir.map(|d| scan_versions(&d.join("node"), &[arch]))
+        .unwrap_or_default()
+}
+
+// Homebrew's Cellar keeps one folder per version for both `node` and `node@NN` formulae
+fn homebrew() -> Vec<(String, PathBuf)> {
+    let mut out = Vec::new();
+    for cellar in ["/opt/homebrew/Cellar", "/usr/local/Cellar", "/home/linuxbrew/.linuxbrew/Cellar"] {
+        if let Ok(entries) = fs::read_dir(cellar) {
+            for entry in entries.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if name == "node" || name.starts_with("node@") {
+                    out.extend(scan_versions(&entry.path(), &[]));
+                }
+            }
+        }
+    }
+    out
+}
+
+// Distro packages and official installers, whose path carries no version
+fn system() -> Vec<(String, PathBuf)> {
+    let mut candidates: Vec<PathBuf> = Vec::new();
+    if cfg!(target_os = "windows") {
+        for var in ["ProgramFiles", "ProgramFiles(x86)"] {
+            if let Some(dir) = env_dir(var) {
+                candidates.push(dir.join("nodejs"));
+            }
+        }
+    } else {
+        candidates.extend(["/usr/bin", "/usr/local/bin", "/opt/homebrew/bin"].iter().map(PathBuf::from));
+    }
+
+    candidates
+        .into_iter()
+        .filter(|dir| dir.join(NODE_EXE).is_file())
+        .filter_map(|dir| {
+            let version = crate::nvm::get_node_version(dir.join(NODE_EXE).to_string_lossy().to_string())?;
+            Some((version, dir))
+        })
+        .collect()
+}
+
+// All Node installations, with symlinked or repeated binaries reported once
+pub fn find_all() -> Vec<FoundNode> {
+    let discovered = SOURCES
+        .iter()
+        .flat_map(|(source, discover)| discover().into_iter().map(move |(version, bin_dir)| (*source, version, bin_dir)));
+    dedupe(discovered)
+}
+
+// Drops binaries already reported under an earlier source. Besides symlinks, `n` copies its
+// active version into /usr/local/bin, so a system binary with the same version and size as
+// a manager's is taken to be that copy
+fn dedupe(discovered: impl IntoIterator<Item = (&'static str, String, PathBuf)>) -> Vec<FoundNode> {
+    let mut seen = HashSet::new();
+    let mut managed_copies = HashSet::new();
+    let mut found = Vec::new();
+
+    for (source, version, bin_dir) in discovered {
+        let binary = bin_dir.join(NODE_EXE);
+        let size = fs::metadata(&binary).map(|m| m.len()).ok();
+        let key = fs::canonicalize(&binary).unwrap_or(binary);
+        if !seen.insert(key) {
+            continue;
+        }
+        let copy = (version.clone(), size);
+        if source == "system" && size.is_some() && managed_copies.contains(&copy) {
+            continue;
+        }
+        if source != "system" {
+            managed_copies.insert(copy);
+        }
+        found.push(FoundNode { version, bin_dir, source });
+    }
+    found
+}
+
+// Highest installed version satisfying a spec such as "18", "^20.1" or "lts/hydrogen"
+pub fn resolve_installed(input: &str) -> Result<Option<FoundNode>, String> {
+    let spec = NodeSpec::parse(input).ok_or_else(|| format!("Invalid Node.js version: {}", input))?;
+    let codenames = if spec.needs_lts() { node_dist::lts_codenames() } else { HashMap::new() };
+
+    let installed = find_all();
+    let best = spec.resolve(
+        installed
+            .iter()
+            .map(|f| (f.version.as_str(), codenames.get(&f.version).map(|c| c.as_str()))),
+    );
+    Ok(best.and_then(|v| installed.into_iter().find(|f| Version::parse(&f.version).as_ref() == Some(&v))))
+}
+
+// CPU architecture a node binary was built for, in Node's naming ("x64", "arm64", ...),
+// read from its ELF, Mach-O or PE header
+pub fn binary_arch(binary: &Path) -> Option<&'static str> {
+    let mut header = [0u8; 512];
+    let len = fs::File::open(binary).and_then(|mut f| f.read(&mut header)).ok()?;
+    let header = &header[..len];
+    let u16_le = |at: usize| header.get(at..at + 2).map(|b| u16::from_le_bytes([b[0], b[1]]));
+    let u32_le = |at: usize| header.get(at..at + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]));
+
+    if header.starts_with(b"\x7fELF") {
+        let machine = match header.get(5) {
+            Some(2) => header.get(18..20).map(|b| u16::from_be_bytes([b[0], b[1]]))?,
+            _ => u16_le(18)?,
+        };
+        return match machine {
+            0x3e => Some("x64"),
+            0xb7 => Some("arm64"),
+            0x03 => Some("x86"),
+            0x28 => Some("arm"),
+            0x15 => Some("ppc64"),
+            0x16 => Some("s390x"),
+            _ => None,
+        };
+    }
+    if header.starts_with(&[0xcf, 0xfa, 0xed, 0xfe]) {
+        return match u32_le(4)? {
+            0x0100_0007 => Some("x64"),
+            0x0100_000c => Some("arm64"),
+            _ => None,
+        };
+    }
+    if header.starts_with(&[0xca, 0xfe, 0xba, 0xbe]) {
+        return Some("universal");
+    }
+    if header.starts_with(b"MZ") {
+        let pe = u32_le(0x3c)? as usize;
+        if header.get(pe..pe + 4)? != b"PE\0\0" {
+            return None;
+        }
+        return match u16_le(pe + 4)? {
+            0x8664 => Some("x64"),
+            0xaa64 => Some("arm64"),
+            0x014c => Some("x86"),
+            _ => None,
+        };
+    }
+    None
+}
+
+// The host architecture in the same naming as binary_arch
+pub fn host_arch() -> &'static str {
+    match env::consts::ARCH {
+        "x86_64" => "x64",
+        "aarch64" => "arm64",
+        "x86" => "x86",
+        "powerpc64" => "ppc64",
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn install(root: &Path, name: &str, binary: &[u8]) -> PathBuf {
+        let dir = root.join(name);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(NODE_EXE), binary).unwrap();
+        dir
+    }
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = env::temp_dir().join(format!("fp-discovery-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        root
+    }
+
+    #[test]
+    fn drops_system_copy_of_a_managed_binary() {
+        let root = temp_root("copy");
+        let n = install(&root, "n", b"node 20");
+        let copy = install(&root, "usr-local-bin", b"node 20");
+        let other = install(&root, "usr-bin", b"node 18");
+        let found = dedupe([
+            ("n", "v20.11.1".to_string(), n),
+            ("system", "v20.11.1".to_string(), copy),
+            ("system", "v18.19.0".to_string(), other),
+        ]);
+        let sources: Vec<_> = found.iter().map(|f| (f.source, f.version.as_str())).collect();
+        assert_eq!(sources, [("n", "v20.11.1"), ("system", "v18.19.0")]);
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn keeps_the_same_version_from_two_managers() {
+        let root = temp_root("managers");
+        let nvm = install(&root, "nvm", b"node 20");
+        let fnm = install(&root, "fnm", b"node 20");
+        let found = dedupe([("nvm", "v20.11.1".to_string(), nvm), ("fnm", "v20.11.1".to_string(), fnm)]);
+        assert_eq!(found.len(), 2);
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn reports_a_symlinked_binary_once() {
+        let root = temp_root("symlink");
+        let nvm = install(&root, "nvm", b"node 20");
+        let link = root.join("bin");
+        fs::create_dir_all(&link).unwrap();
+        std::os::unix::fs::symlink(nvm.join(NODE_EXE), link.join(NODE_EXE)).unwrap();
+        let found = dedupe([("nvm", "v20.11.1".to_string(), nvm), ("system", "v20.11.1".to_string(), link)]);
+        assert_eq!(found.len(), 1);
+        let _ = fs::remove_dir_all(root);
+    }
+
+    fn arch_of(name: &str, header: &[u8]) -> Option<&'static str> {
+        let root = temp_root(name);
+        let dir = install(&root, "bin", header);
+        let arch = binary_arch(&dir.join(NODE_EXE));
+        let _ = fs::remove_dir_all(root);
+        arch
+    }
+
+    fn elf(class_data: [u8; 2], machine: [u8; 2]) -> Vec<u8> {
+        let mut header = vec![0u8; 64];
+        header[..4].copy_from_slice(b"\x7fELF");
+        header[4..6].copy_from_slice(&class_data);
+        header[18..20].copy_from_slice(&machine);
+        header
+    }
+
+    fn pe(machine: u16) -> Vec<u8> {
+        let mut header = vec![0u8; 0x100];
+        header[..2].copy_from_slice(b"MZ");
+        header[0x3c..0x40].copy_from_slice(&0x80u32.to_le_bytes());
+        header[0x80..0x84].copy_from_slice(b"PE\0\0");
+        header[0x84..0x86].copy_from_slice(&machine.to_le_bytes());
+        header
+    }
+
+    #[test]
+    fn reads_elf_architecture() {
+        assert_eq!(arch_of("elf-x64", &elf([2, 1], [0x3e, 0])), Some("x64"));
+        assert_eq!(arch_of("elf-arm64", &elf([2, 1], [0xb7, 0])), Some("arm64"));
+        assert_eq!(arch_of("elf-arm", &elf([1, 1], [0x28, 0])), Some("arm"));
+        // Big-endian s390x stores the machine most significant byte first
+        assert_eq!(arch_of("elf-s390x", &elf([2, 2], [0, 0x16])), Some("s390x"));
+        assert_eq!(arch_of("elf-riscv", &elf([2, 1], [0xf3, 0])), None);
+    }
+
+    #[test]
+    fn reads_mach_o_architecture() {
+        let mach_o = |cpu: u32| [[0xcf, 0xfa, 0xed, 0xfe], cpu.to_le_bytes(), [0; 4], [0; 4]].concat();
+        assert_eq!(arch_of("macho-x64", &mach_o(0x0100_0007)), Some("x64"));
+        assert_eq!(arch_of("macho-arm64", &mach_o(0x0100_000c)), Some("arm64"));
+        assert_eq!(arch_of("macho-fat", &[0xca, 0xfe, 0xba, 0xbe, 0, 0, 0, 2]), Some("universal"));
+    }
+
+    #[test]
+    fn reads_pe_architecture() {
+        assert_eq!(arch_of("pe-x64", &pe(0x8664)), Some("x64"));
+        assert_eq!(arch_of("pe-arm64", &pe(0xaa64)), Some("arm64"));
+        assert_eq!(arch_of("pe-x86", &pe(0x014c)), Some("x86"));
+        let mut dos_only = pe(0x8664);
+        dos_only[0x80..0x84].copy_from_slice(b"XXXX");
+        assert_eq!(arch_of("pe-dos", &dos_only), None);
+    }
+
+    #[test]
+    fn rejects_unknown_or_truncated_files() {
+        assert_eq!(arch_of("script", b"#!/bin/sh\nexec node \"$@\"\n"), None);
+        assert_eq!(arch_of("truncated-elf", b"\x7fELF\x02\x01"), None);
+        assert_eq!(arch_of("truncated-pe", b"MZ"), None);
+        assert_eq!(binary_arch(Path::new("/nonexistent/node")), None);
+    }
+}
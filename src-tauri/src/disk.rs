@@ -0,0 +1,220 @@
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use tauri::{AppHandle, Emitter, State};
+
+use crate::runner::{is_project_running, ProcessState};
+
+// Folders that can always be regenerated by an install or a build
+const CLEANABLE_FOLDERS: &[&str] = &["node_modules", "dist", ".next", ".nuxt", ".turbo", ".cache"];
+
+// Emit a progress event every this many files while walking a folder
+const PROGRESS_INTERVAL: u64 = 2000;
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderUsage {
+    name: String,
+    bytes: u64,
+    files: u64,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectDiskUsage {
+    path: String,
+    folders: Vec<FolderUsage>,
+    total_bytes: u64,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct DiskUsageProgress<'a> {
+    path: &'a str,
+    folder: &'a str,
+    bytes: u64,
+    files: u64,
+    done: bool,
+}
+
+// Symlinks are counted as links, not followed, so pnpm's store links are not counted twice
+fn walk_size(dir: &Path, usage: &mut FolderUsage, on_progress: &mut dyn FnMut(&FolderUsage)) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for entry in entries.flatten() {
+        let meta = match entry.metadata() {
+            Ok(meta) => meta,
+            Err(_) => continue,
+        };
+        if meta.is_dir() {
+            walk_size(&entry.path(), usage, on_progress);
+        } else {
+            usage.bytes += meta.len();
+            usage.files += 1;
+            if usage.files.is_multiple_of(PROGRESS_INTERVAL) {
+                on_progress(usage);
+            }
+        }
+    }
+}
+
+// Total size of the files below `dir`, counted the same way as the usage report
+pub(crate) fn dir_size(dir: &Path) -> u64 {
+    let mut usage = FolderUsage { name: String::new(), bytes: 0, files: 0 };
+    walk_size(dir, &mut usage, &mut |_| {});
+    usage.bytes
+}
+
+fn project_usage(path: &str, on_progress: &mut dyn FnMut(&FolderUsage, bool)) -> ProjectDiskUsage {
+    let mut folders = Vec::new();
+
+    for name in CLEANABLE_FOLDERS {
+        let dir = Path::new(path).join(name);
+        if !dir.is_dir() {
+            continue;
+        }
+
+        let mut usage = FolderUsage { name: name.to_string(), bytes: 0, files: 0 };
+        walk_size(&dir, &mut usage, &mut |u| on_progress(u, false));
+        on_progress(&usage, true);
+        folders.push(usage);
+    }
+
+    ProjectDiskUsage {
+        path: path.to_string(),
+        total_bytes: folders.iter().map(|f| f.bytes).sum(),
+        folders,
+    }
+}
+
+#[tauri::command]
+pub async fn get_disk_usage(app: AppHandle, paths: Vec<String>) -> Result<Vec<ProjectDiskUsage>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        paths
+            .iter()
+            .map(|path| {
+                project_usage(path, &mut |u, done| {
+                    let _ = app.emit(
+                        "disk-usage-progress",
+                        DiskUsageProgress { path, folder: &u.name, bytes: u.bytes, files: u.files, done },
+                    );
+                })
+            })
+            .collect()
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn clean_project(state: State<'_, ProcessState>, path: String, folders: Vec<String>) -> Result<u64, String> {
+    let project_path = Path::new(&path).to_path_buf();
+    check_cleanable(&state, &project_path, &folders)?;
+    tauri::async_runtime::spawn_blocking(move || remove_folders(&project_path, &folders))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+fn check_cleanable(state: &ProcessState, project_path: &Path, folders: &[String]) -> Result<(), String> {
+    if let Some(name) = folders.iter().find(|f| !CLEANABLE_FOLDERS.contains(&f.as_str())) {
+        return Err(format!("Refusing to delete '{}': not a cleanable folder", name));
+    }
+    if !project_path.join("package.json").exists() {
+        return Err("package.json not found".to_string());
+    }
+    if is_project_running(state, project_path) {
+        return Err("Project has running scripts, stop them before cleaning".to_string());
+    }
+    Ok(())
+}
+
+fn remove_folders(project_path: &Path, folders: &[String]) -> Result<u64, String> {
+    let mut freed = 0;
+    for name in folders {
+        let dir = project_path.join(name);
+        let meta = match fs::symlink_metadata(&dir) {
+            Ok(meta) => meta,
+            Err(_) => continue,
+        };
+
+        // remove_dir_all only removes a symlinked folder's link, never its target
+        if meta.is_dir() {
+            freed += dir_size(&dir);
+        }
+        fs::remove_dir_all(&dir).map_err(|e| format!("Failed to delete {}: {}", name, e))?;
+    }
+    Ok(freed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn project(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("fp-disk-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("node_modules/pkg/lib")).unwrap();
+        fs::create_dir_all(dir.join("dist")).unwrap();
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("package.json"), "{}").unwrap();
+        fs::write(dir.join("node_modules/pkg/index.js"), [0u8; 100]).unwrap();
+        fs::write(dir.join("node_modules/pkg/lib/a.js"), [0u8; 50]).unwrap();
+        fs::write(dir.join("dist/bundle.js"), [0u8; 30]).unwrap();
+        fs::write(dir.join("src/main.js"), [0u8; 1000]).unwrap();
+        dir
+    }
+
+    #[test]
+    fn measures_only_cleanable_folders() {
+        let dir = project("usage");
+        let mut finished = Vec::new();
+        let usage = project_usage(dir.to_str().unwrap(), &mut |u, done| {
+            if done {
+                finished.push(u.name.clone());
+            }
+        });
+
+        let sizes: Vec<(&str, u64, u64)> = usage.folders.iter().map(|f| (f.name.as_str(), f.bytes, f.files)).collect();
+        assert_eq!(sizes, vec![("node_modules", 150, 2), ("dist", 30, 1)]);
+        assert_eq!(usage.total_bytes, 180);
+        assert_eq!(finished, vec!["node_modules", "dist"]);
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn does_not_follow_or_delete_through_symlinks() {
+        let dir = project("symlink");
+        let outside = dir.join("src");
+        std::os::unix::fs::symlink(&outside, dir.join("node_modules/linked")).unwrap();
+        std::os::unix::fs::symlink(&outside, dir.join(".cache")).unwrap();
+        // The link itself counts, the 1000 bytes it points at do not
+        let expected = 150 + fs::symlink_metadata(dir.join("node_modules/linked")).unwrap().len();
+        assert_eq!(dir_size(&dir.join("node_modules")), expected);
+
+        let freed = remove_folders(&dir, &["node_modules".to_string(), ".cache".to_string()]).unwrap();
+        assert_eq!(freed, expected);
+        assert!(!dir.join("node_modules").exists());
+        assert!(fs::symlink_metadata(dir.join(".cache")).is_err());
+        assert!(outside.join("main.js").exists());
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn refuses_folders_outside_the_allowlist() {
+        let dir = project("refuse");
+        let state = ProcessState::new();
+        for name in ["src", "..", "../other", "node_modules/../src", ""] {
+            let err = check_cleanable(&state, &dir, &["dist".to_string(), name.to_string()]).unwrap_err();
+            assert!(err.contains("not a cleanable folder"), "{}", err);
+        }
+        assert!(check_cleanable(&state, &dir, &["dist".to_string()]).is_ok());
+
+        fs::remove_file(dir.join("package.json")).unwrap();
+        assert_eq!(check_cleanable(&state, &dir, &["dist".to_string()]), Err("package.json not found".to_string()));
+        let _ = fs::remove_dir_all(dir);
+    }
+}
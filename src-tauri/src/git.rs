@@ -0,0 +1,405 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, State};
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+#[cfg(target_os = "windows")]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+const WATCH_INTERVAL: Duration = Duration::from_secs(2);
+// Concurrent `git status` runs when refreshing many projects at once
+const STATUS_WORKERS: usize = 4;
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitCommit {
+    hash: String,
+    subject: String,
+    author: String,
+    timestamp: i64,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitStatus {
+    branch: Option<String>,
+    detached: bool,
+    upstream: Option<String>,
+    ahead: u32,
+    behind: u32,
+    dirty: bool,
+    staged: u32,
+    unstaged: u32,
+    untracked: u32,
+    conflicted: u32,
+    last_commit: Option<GitCommit>,
+}
+
+// A watched project: its git directory (resolved once, on registration) and last seen HEAD signature
+#[derive(Clone)]
+pub struct WatchedRepo {
+    git_dir: Option<PathBuf>,
+    head: Option<String>,
+}
+
+pub struct GitWatchState {
+    // Project path -> watched repository
+    pub watched: Arc<Mutex<HashMap<String, WatchedRepo>>>,
+    pub is_running: Arc<AtomicBool>,
+}
+
+impl GitWatchState {
+    pub fn new() -> Self {
+        Self {
+            watched: Arc::new(Mutex::new(HashMap::new())),
+            is_running: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+fn git(path: &Path, args: &[&str]) -> Option<String> {
+    let mut cmd = Command::new("git");
+    cmd.args(args).current_dir(path).env("GIT_OPTIONAL_LOCKS", "0");
+
+    #[cfg(target_os = "windows")]
+    cmd.creation_flags(CREATE_NO_WINDOW);
+
+    let output = cmd.output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn read_status(path: &Path) -> Option<GitStatus> {
+    let output = git(path, &["status", "--porcelain=v2", "--branch"])?;
+
+    let mut status = GitStatus {
+        branch: None,
+        detached: false,
+        upstream: None,
+        ahead: 0,
+        behind: 0,
+        dirty: false,
+        staged: 0,
+        unstaged: 0,
+        untracked: 0,
+        conflicted: 0,
+        last_commit: None,
+    };
+
+    for line in output.lines() {
+        if let Some(head) = line.strip_prefix("# branch.head ") {
+            status.detached = head == "(detached)";
+            if !status.detached {
+                status.branch = Some(head.to_string());
+            }
+        } else if let Some(upstream) = line.strip_prefix("# branch.upstream ") {
+            status.upstream = Some(upstream.to_string());
+        } else if let Some(ab) = line.strip_prefix("# branch.ab ") {
+            for part in ab.split_whitespace() {
+                if let Some(n) = part.strip_prefix('+') {
+                    status.ahead = n.parse().unwrap_or(0);
+                } else if let Some(n) = part.strip_prefix('-') {
+                    status.behind = n.parse().unwrap_or(0);
+                }
+            }
+        } else if line.starts_with("1 ") || line.starts_with("2 ") {
+            // "1 XY ...": X is the index state, Y the worktree state, '.' meaning unchanged
+            let xy = line.as_bytes();
+            if xy.len() > 3 {
+                if xy[2] != b'.' {
+                    status.staged += 1;
+                }
+                if xy[3] != b'.' {
+                    status.unstaged += 1;
+                }
+            }
+        } else if line.starts_with("u ") {
+            status.conflicted += 1;
+        } else if line.starts_with("? ") {
+            status.untracked += 1;
+        }
+    }
+
+    status.dirty = status.staged + status.unstaged + status.untracked + status.conflicted > 0;
+    status.last_commit = read_last_commit(path);
+    Some(status)
+}
+
+fn read_last_commit(path: &Path) -> Option<GitCommit> {
+    let output = git(path, &["log", "-1", "--format=%H%x1f%s%x1f%an%x1f%ct"])?;
+    let mut fields = output.trim_end().split('\x1f');
+    Some(GitCommit {
+        hash: fields.next()?.to_string(),
+        subject: fields.next()?.to_string(),
+        author: fields.next()?.to_string(),
+        timestamp: fields.next()?.parse().ok()?,
+    })
+}
+
+// Asks git for the repository's git directory, so projects in a subdirectory of a repo,
+// worktrees and submodules all resolve to the directory that actually holds HEAD
+fn git_dir(path: &Path) -> Option<PathBuf> {
+    let dir = git(path, &["rev-parse", "--absolute-git-dir"])?;
+    let dir = dir.trim();
+    (!dir.is_empty()).then(|| PathBuf::from(dir))
+}
+
+// Cheap HEAD fingerprint read straight from the git directory, so polling never spawns git
+fn head_signature(dir: &Path) -> Option<String> {
+    let head = fs::read_to_string(dir.join("HEAD")).ok()?;
+    let head = head.trim();
+
+    let reference = match head.strip_prefix("ref:") {
+        Some(reference) => reference.trim(),
+        None => return Some(head.to_string()),
+    };
+
+    // Worktrees keep branch refs in the main repository's git directory
+    let common = fs::read_to_string(dir.join("commondir"))
+        .map(|c| dir.join(c.trim()))
+        .unwrap_or_else(|_| dir.to_path_buf());
+
+    let sha = fs::read_to_string(common.join(reference))
+        .map(|s| s.trim().to_string())
+        .ok()
+        .or_else(|| {
+            let packed = fs::read_to_string(common.join("packed-refs")).ok()?;
+            packed
+                .lines()
+                .filter_map(|l| l.split_once(' '))
+                .find(|(_, name)| *name == reference)
+                .map(|(sha, _)| sha.to_string())
+        })
+        .unwrap_or_default();
+
+    Some(format!("{}@{}", reference, sha))
+}
+
+#[tauri::command]
+pub async fn get_git_status(path: String) -> Result<Option<GitStatus>, String> {
+    tauri::async_runtime::spawn_blocking(move || read_status(Path::new(&path)))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_git_status_batch(paths: Vec<String>) -> Result<HashMap<String, Option<GitStatus>>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        // A few workers pull paths off a shared queue, so a long project list never
+        // means one git process per project at once
+        let queue = Arc::new(Mutex::new(paths.into_iter()));
+        let handles: Vec<_> = (0..STATUS_WORKERS)
+            .map(|_| {
+                let queue = queue.clone();
+                thread::spawn(move || {
+                    let mut results = Vec::new();
+                    loop {
+                        let next = match queue.lock() {
+                            Ok(mut paths) => paths.next(),
+                            Err(_) => None,
+                        };
+                        let Some(path) = next else { break };
+                        let status = read_status(Path::new(&path));
+                        results.push((path, status));
+                    }
+                    results
+                })
+            })
+            .collect();
+
+        handles.into_iter().filter_map(|h| h.join().ok()).flatten().collect()
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+// Replaces the set of watched projects; a "git-head-changed" event is emitted when a HEAD moves
+#[tauri::command]
+pub async fn watch_git_repos(app: AppHandle, state: State<'_, GitWatchState>, paths: Vec<String>) -> Result<(), String> {
+    let watched = state.watched.clone();
+    let is_running = state.is_running.clone();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        set_watched(&watched, paths)?;
+
+        if is_running.swap(true, Ordering::SeqCst) {
+            return Ok(());
+        }
+
+        thread::spawn(move || loop {
+            thread::sleep(WATCH_INTERVAL);
+
+            for path in poll(&watched) {
+                let status = read_status(Path::new(&path));
+                let _ = app.emit(
+                    "git-head-changed",
+                    serde_json::json!({ "path": path, "status": status }),
+                );
+            }
+        });
+
+        Ok(())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+fn watch(path: &Path) -> WatchedRepo {
+    let git_dir = git_dir(path);
+    let head = git_dir.as_deref().and_then(head_signature);
+    WatchedRepo { git_dir, head }
+}
+
+// Keeps what is known about paths that stay watched; new ones are resolved through git
+// without holding the lock, so polling is never stuck behind a slow `git rev-parse`
+fn set_watched(watched: &Mutex<HashMap<String, WatchedRepo>>, paths: Vec<String>) -> Result<(), String> {
+    let previous = watched.lock().map_err(|e| e.to_string())?.clone();
+    let repos: HashMap<String, WatchedRepo> = paths
+        .into_iter()
+        .map(|path| {
+            let repo = previous.get(&path).cloned().unwrap_or_else(|| watch(Path::new(&path)));
+            (path, repo)
+        })
+        .collect();
+    *watched.lock().map_err(|e| e.to_string())? = repos;
+    Ok(())
+}
+
+// Paths whose HEAD moved since the last poll. Paths that were not a repository yet are
+// resolved again each time, so a later `git init` or clone is picked up
+fn poll(watched: &Mutex<HashMap<String, WatchedRepo>>) -> Vec<String> {
+    let unresolved: Vec<String> = match watched.lock() {
+        Ok(lock) => lock.iter().filter(|(_, repo)| repo.git_dir.is_none()).map(|(path, _)| path.clone()).collect(),
+        Err(_) => return Vec::new(),
+    };
+    let resolved: Vec<(String, PathBuf)> = unresolved
+        .into_iter()
+        .filter_map(|path| git_dir(Path::new(&path)).map(|dir| (path, dir)))
+        .collect();
+
+    let Ok(mut lock) = watched.lock() else {
+        return Vec::new();
+    };
+    for (path, dir) in resolved {
+        if let Some(repo) = lock.get_mut(&path) {
+            repo.git_dir.get_or_insert(dir);
+        }
+    }
+    lock.iter_mut()
+        .filter_map(|(path, repo)| {
+            let current = repo.git_dir.as_deref().and_then(head_signature);
+            if current != repo.head {
+                repo.head = current;
+                Some(path.clone())
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com", "-c", "init.defaultBranch=main"])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?}", args);
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("fp-git-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn repo(name: &str) -> PathBuf {
+        let dir = temp_dir(name);
+        run(&dir, &["init", "-q"]);
+        fs::write(dir.join("README.md"), "hello").unwrap();
+        run(&dir, &["add", "."]);
+        run(&dir, &["commit", "-q", "-m", "Initial commit"]);
+        dir
+    }
+
+    #[test]
+    fn reads_branch_changes_and_last_commit() {
+        let dir = repo("status");
+        fs::write(dir.join("README.md"), "changed").unwrap();
+        fs::write(dir.join("new.txt"), "new").unwrap();
+        fs::write(dir.join("staged.txt"), "staged").unwrap();
+        run(&dir, &["add", "staged.txt"]);
+
+        let status = read_status(&dir).unwrap();
+        assert_eq!(status.branch.as_deref(), Some("main"));
+        assert!(!status.detached);
+        assert_eq!((status.staged, status.unstaged, status.untracked), (1, 1, 1));
+        assert!(status.dirty);
+        assert_eq!(status.last_commit.unwrap().subject, "Initial commit");
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn resolves_the_git_dir_from_a_subdirectory() {
+        let dir = repo("subdir");
+        let package = dir.join("packages").join("app");
+        fs::create_dir_all(&package).unwrap();
+        assert_eq!(git_dir(&package), Some(fs::canonicalize(dir.join(".git")).unwrap()));
+        assert!(read_status(&package).is_some());
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn head_signature_follows_commits_and_checkouts() {
+        let dir = repo("head");
+        let git = git_dir(&dir).unwrap();
+        let first = head_signature(&git).unwrap();
+        assert!(first.starts_with("refs/heads/main@"));
+
+        run(&dir, &["commit", "-q", "--allow-empty", "-m", "Second"]);
+        let second = head_signature(&git).unwrap();
+        assert_ne!(first, second);
+
+        run(&dir, &["checkout", "-q", "--detach"]);
+        assert!(!head_signature(&git).unwrap().starts_with("refs/"));
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn poll_reports_moved_heads_and_new_repositories() {
+        let existing = repo("poll");
+        let later = temp_dir("poll-later");
+        let watched = Mutex::new(HashMap::new());
+        set_watched(&watched, vec![existing.to_string_lossy().to_string(), later.to_string_lossy().to_string()]).unwrap();
+        assert!(poll(&watched).is_empty());
+
+        run(&existing, &["commit", "-q", "--allow-empty", "-m", "Moved"]);
+        assert_eq!(poll(&watched), vec![existing.to_string_lossy().to_string()]);
+
+        run(&later, &["init", "-q"]);
+        run(&later, &["commit", "-q", "--allow-empty", "-m", "First"]);
+        assert_eq!(poll(&watched), vec![later.to_string_lossy().to_string()]);
+        assert!(poll(&watched).is_empty());
+
+        let _ = fs::remove_dir_all(existing);
+        let _ = fs::remove_dir_all(later);
+    }
+}
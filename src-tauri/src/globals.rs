@@ -0,0 +1,281 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tauri::{command, AppHandle, Emitter};
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+#[cfg(target_os = "windows")]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+#[cfg(target_os = "windows")]
+const NODE_EXE: &str = "node.exe";
+#[cfg(not(target_os = "windows"))]
+const NODE_EXE: &str = "node";
+
+// Shipped with Node itself, so never installed or removed as part of a migration
+const BUNDLED: &[&str] = &["npm", "corepack"];
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobalPackage {
+    name: String,
+    version: String,
+    bundled: bool,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobalPackageResult {
+    name: String,
+    success: bool,
+    error: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct GlobalPackageProgress<'a> {
+    // "install", "uninstall" or "migrate"
+    operation: &'a str,
+    package: &'a str,
+    index: usize,
+    total: usize,
+    // "running", "done" or "failed"
+    status: &'a str,
+    error: Option<&'a str>,
+}
+
+#[derive(Deserialize)]
+struct PackageManifest {
+    name: Option<String>,
+    version: Option<String>,
+}
+
+// The install prefix of a Node version, given the directory holding its binary as listed
+// by get_nvm_list: `<prefix>/bin` on Unix, the prefix itself on Windows
+pub(crate) fn prefix(node_path: &str) -> Result<PathBuf, String> {
+    let dir = Path::new(node_path);
+    if !dir.join(NODE_EXE).is_file() {
+        return Err(format!("Node.js not found in {}", node_path));
+    }
+    if cfg!(target_os = "windows") {
+        Ok(dir.to_path_buf())
+    } else {
+        dir.parent().map(|p| p.to_path_buf()).ok_or_else(|| format!("Invalid Node.js path: {}", node_path))
+    }
+}
+
+pub(crate) fn global_modules(prefix: &Path) -> PathBuf {
+    if cfg!(target_os = "windows") {
+        prefix.join("node_modules")
+    } else {
+        prefix.join("lib").join("node_modules")
+    }
+}
+
+fn read_manifest(dir: &Path) -> Option<GlobalPackage> {
+    let content = fs::read_to_string(dir.join("package.json")).ok()?;
+    let manifest: PackageManifest = serde_json::from_str(&content).ok()?;
+    let name = manifest.name?;
+    Some(GlobalPackage {
+        bundled: BUNDLED.contains(&name.as_str()),
+        version: manifest.version.unwrap_or_default(),
+        name,
+    })
+}
+
+fn list(node_path: &str) -> Result<Vec<GlobalPackage>, String> {
+    let modules = global_modules(&prefix(node_path)?);
+    let mut packages = Vec::new();
+
+    let entries = match fs::read_dir(&modules) {
+        Ok(entries) => entries,
+        Err(_) => return Ok(packages),
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with('.') {
+            continue;
+        }
+        // Scoped packages live one level deeper, e.g. @vue/cli
+        if name.starts_with('@') {
+            if let Ok(scoped) = fs::read_dir(entry.path()) {
+                packages.extend(scoped.flatten().filter_map(|e| read_manifest(&e.path())));
+            }
+        } else if let Some(package) = read_manifest(&entry.path()) {
+            packages.push(package);
+        }
+    }
+
+    packages.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(packages)
+}
+
+// Package specs go straight to npm's argv; anything starting with '-' would be read as a flag
+fn validate_spec(spec: &str) -> Result<(), String> {
+    if spec.is_empty() || spec.starts_with('-') || spec.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err(format!("Invalid package name: {}", spec));
+    }
+    Ok(())
+}
+
+// Runs the version's own npm-cli.js with its node, so neither a shell nor another
+// version's npm shim gets involved, and pins --prefix against a user-level .npmrc
+fn npm(node_path: &str, args: &[&str]) -> Result<(), String> {
+    let prefix = prefix(node_path)?;
+    let npm_cli = global_modules(&prefix).join("npm").join("bin").join("npm-cli.js");
+    if !npm_cli.is_file() {
+        return Err(format!("npm is not installed for the Node.js in {}", node_path));
+    }
+
+    let path = std::env::var_os("PATH").unwrap_or_default();
+    let path = std::env::join_paths(std::iter::once(PathBuf::from(node_path)).chain(std::env::split_paths(&path)))
+        .map_err(|e| e.to_string())?;
+
+    let mut cmd = Command::new(Path::new(node_path).join(NODE_EXE));
+    cmd.arg(&npm_cli)
+        .args(args)
+        .arg("--prefix")
+        .arg(&prefix)
+        .arg("--no-fund")
+        .arg("--no-audit")
+        .env("PATH", path);
+
+    #[cfg(target_os = "windows")]
+    cmd.creation_flags(CREATE_NO_WINDOW);
+
+    let output = cmd.output().map_err(|e| e.to_string())?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let message: Vec<&str> = stderr.lines().filter(|l| l.starts_with("npm ERR!") || l.starts_with("npm error")).collect();
+    Err(if message.is_empty() { stderr.trim().to_string() } else { message.join("\n") })
+}
+
+// Applies `npm <action> -g` to each package in turn, reporting every step as a
+// "global-packages-progress" event; one failing package does not stop the rest
+fn run_each(app: &AppHandle, operation: &str, node_path: &str, action: &str, specs: &[String]) -> Vec<GlobalPackageResult> {
+    let total = specs.len();
+    specs
+        .iter()
+        .enumerate()
+        .map(|(index, spec)| {
+            let emit = |status: &str, error: Option<&str>| {
+                let _ = app.emit(
+                    "global-packages-progress",
+                    GlobalPackageProgress { operation, package: spec, index, total, status, error },
+                );
+            };
+            emit("running", None);
+            let result = validate_spec(spec).and_then(|_| npm(node_path, &[action, "-g", spec]));
+            emit(if result.is_ok() { "done" } else { "failed" }, result.as_ref().err().map(|e| e.as_str()));
+            GlobalPackageResult { name: spec.clone(), success: result.is_ok(), error: result.err() }
+        })
+        .collect()
+}
+
+#[command]
+pub fn list_global_packages(node_path: String) -> Result<Vec<GlobalPackage>, String> {
+    list(&node_path)
+}
+
+#[command]
+pub async fn install_global_packages(app: AppHandle, node_path: String, packages: Vec<String>) -> Result<Vec<GlobalPackageResult>, String> {
+    prefix(&node_path)?;
+    tauri::async_runtime::spawn_blocking(move || run_each(&app, "install", &node_path, "install", &packages))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[command]
+pub async fn uninstall_global_packages(app: AppHandle, node_path: String, packages: Vec<String>) -> Result<Vec<GlobalPackageResult>, String> {
+    if let Some(name) = packages.iter().find(|p| BUNDLED.contains(&p.as_str())) {
+        return Err(format!("{} ships with Node.js and cannot be uninstalled", name));
+    }
+    prefix(&node_path)?;
+    tauri::async_runtime::spawn_blocking(move || run_each(&app, "uninstall", &node_path, "uninstall", &packages))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+// Installs the global packages of one version into another, keeping their versions
+#[command]
+pub async fn migrate_global_packages(app: AppHandle, from_path: String, to_path: String) -> Result<Vec<GlobalPackageResult>, String> {
+    prefix(&to_path)?;
+    let specs: Vec<String> = list(&from_path)?
+        .into_iter()
+        .filter(|p| !p.bundled)
+        .map(|p| if p.version.is_empty() { p.name } else { format!("{}@{}", p.name, p.version) })
+        .collect();
+
+    tauri::async_runtime::spawn_blocking(move || run_each(&app, "migrate", &to_path, "install", &specs))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A fake Node install laid out the way get_nvm_list reports it, returning the binary's directory
+    fn install(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("fp-globals-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let bin = if cfg!(target_os = "windows") { root.clone() } else { root.join("bin") };
+        fs::create_dir_all(&bin).unwrap();
+        fs::write(bin.join(NODE_EXE), "").unwrap();
+        bin
+    }
+
+    fn add_package(bin: &Path, dir: &str, manifest: &str) {
+        let dir = global_modules(&prefix(bin.to_str().unwrap()).unwrap()).join(dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("package.json"), manifest).unwrap();
+    }
+
+    #[test]
+    fn prefix_of_a_node_install() {
+        let bin = install("prefix");
+        let expected = if cfg!(target_os = "windows") { bin.clone() } else { bin.parent().unwrap().to_path_buf() };
+        assert_eq!(prefix(bin.to_str().unwrap()).unwrap(), expected);
+
+        fs::remove_file(bin.join(NODE_EXE)).unwrap();
+        assert!(prefix(bin.to_str().unwrap()).is_err());
+        let _ = fs::remove_dir_all(expected);
+    }
+
+    #[test]
+    fn lists_plain_scoped_and_bundled_packages() {
+        let bin = install("list");
+        add_package(&bin, "npm", r#"{"name":"npm","version":"10.2.0"}"#);
+        add_package(&bin, "typescript", r#"{"name":"typescript","version":"5.3.3"}"#);
+        add_package(&bin, "@vue/cli", r#"{"name":"@vue/cli","version":"5.0.8"}"#);
+        add_package(&bin, ".bin", "{}");
+        add_package(&bin, "broken", "not json");
+
+        let found: Vec<(String, String, bool)> =
+            list(bin.to_str().unwrap()).unwrap().into_iter().map(|p| (p.name, p.version, p.bundled)).collect();
+        assert_eq!(
+            found,
+            vec![
+                ("@vue/cli".to_string(), "5.0.8".to_string(), false),
+                ("npm".to_string(), "10.2.0".to_string(), true),
+                ("typescript".to_string(), "5.3.3".to_string(), false),
+            ]
+        );
+        let _ = fs::remove_dir_all(prefix(bin.to_str().unwrap()).unwrap());
+    }
+
+    #[test]
+    fn validates_package_specs() {
+        for spec in ["typescript", "@vue/cli@5", "pnpm@^8.0.0", "github:user/repo"] {
+            assert!(validate_spec(spec).is_ok(), "{}", spec);
+        }
+        for spec in ["", "-g", "--prefix=/tmp", "a b", "pkg\n--force", "tab\there"] {
+            assert!(validate_spec(spec).is_err(), "{:?}", spec);
+        }
+    }
+}
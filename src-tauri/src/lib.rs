@@ -3,14 +3,23 @@ mod project;
 mod runner;
 mod updater;
 mod system;
+mod deps;
+mod version;
+mod disk;
+mod git;
+mod node_dist;
+mod settings;
+mod discovery;
+mod shim;
+mod globals;
+mod config;
+mod http;
 
 use tauri::Manager;
 
 #[tauri::command]
 fn read_config_file(filename: String) -> Result<String, String> {
-    let mut path = std::env::current_exe().map_err(|e| e.to_string())?;
-    path.pop();
-    path.push(filename);
+    let path = config::config_path(&filename)?;
     
     if !path.exists() {
         return Ok("".to_string());
@@ -21,9 +30,7 @@ fn read_config_file(filename: String) -> Result<String, String> {
 
 #[tauri::command]
 fn write_config_file(filename: String, content: String) -> Result<(), String> {
-    let mut path = std::env::current_exe().map_err(|e| e.to_string())?;
-    path.pop();
-    path.push(filename);
+    let path = config::config_path(&filename)?;
     
     std::fs::write(path, content).map_err(|e| e.to_string())
 }
@@ -39,8 +46,16 @@ pub fn run() {
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
+        .setup(|app| {
+            let app_config_dir = app.path().app_config_dir()?;
+            let dir = config::init(app_config_dir)?;
+            println!("Configuration directory: {:?}", dir);
+            Ok(())
+        })
         .manage(runner::ProcessState::new())
         .manage(updater::UpdateState::new())
+        .manage(git::GitWatchState::new())
+        .manage(nvm::NvmState::new())
         .invoke_handler(tauri::generate_handler![
             nvm::get_nvm_list,
             nvm::get_node_version,
@@ -48,14 +63,41 @@ pub fn run() {
             nvm::install_node,
             nvm::uninstall_node,
             nvm::use_node,
+            nvm::cancel_nvm_operation,
+            shim::get_default_node_status,
+            globals::list_global_packages,
+            globals::install_global_packages,
+            globals::uninstall_global_packages,
+            globals::migrate_global_packages,
+            node_dist::install_node_native,
+            node_dist::get_remote_node_versions,
+            node_dist::get_node_mirror,
+            node_dist::set_node_mirror,
+            node_dist::test_node_mirror,
+            http::get_http_settings,
+            http::set_http_settings,
             project::scan_project,
+            project::check_install_status,
+            project::get_node_recommendation,
+            deps::check_dependency_versions,
+            disk::get_disk_usage,
+            disk::clean_project,
+            git::get_git_status,
+            git::get_git_status_batch,
+            git::watch_git_repos,
             runner::run_project_command,
             runner::stop_project_command,
+            runner::install_dependencies,
             runner::open_in_editor,
             runner::open_folder,
             runner::open_url,
             updater::install_update,
+            updater::get_update_asset,
+            updater::check_for_update,
             updater::cancel_update,
+            updater::get_update_backup,
+            updater::rollback_update,
+            updater::take_update_report,
             system::set_context_menu,
             system::check_context_menu,
             system::is_context_menu_supported,
@@ -0,0 +1,508 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+use tauri::{command, AppHandle, Emitter};
+
+use crate::http::HttpClient;
+use crate::settings;
+use crate::version::{NodeSpec, Version};
+
+pub const DEFAULT_DIST_URL: &str = "https://nodejs.org/dist";
+
+// How long a cached index.json is used before asking the server again
+const INDEX_MAX_AGE: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct InstallProgress<'a> {
+    version: &'a str,
+    // "download", "verify", "extract" or "done"
+    stage: &'a str,
+    downloaded: u64,
+    total: Option<u64>,
+}
+
+// App-managed data lives outside nvm, e.g. ~/.local/share/fp-node-manager on Linux
+pub fn managed_root() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|d| d.join("fp-node-manager"))
+}
+
+pub fn versions_dir() -> Option<PathBuf> {
+    managed_root().map(|r| r.join("versions"))
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteNodeVersion {
+    pub version: String,
+    pub date: String,
+    pub npm: Option<String>,
+    pub v8: Option<String>,
+    // LTS codename such as "Iron", None for Current releases
+    pub lts: Option<String>,
+    pub security: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteVersionList {
+    versions: Vec<RemoteNodeVersion>,
+    // True when the list came from the on-disk cache because the server was unreachable
+    offline: bool,
+}
+
+// One entry of the dist index.json; "lts" is either false or the codename
+#[derive(Deserialize)]
+struct IndexEntry {
+    version: String,
+    date: String,
+    npm: Option<String>,
+    v8: Option<String>,
+    lts: serde_json::Value,
+    #[serde(default)]
+    security: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MirrorTestResult {
+    url: String,
+    ok: bool,
+    status: Option<u16>,
+    latency_ms: u64,
+    error: Option<String>,
+}
+
+// The configured distribution mirror, or nodejs.org when none is set
+pub fn dist_url() -> String {
+    settings::load()
+        .node_mirror
+        .filter(|m| !m.is_empty())
+        .unwrap_or_else(|| DEFAULT_DIST_URL.to_string())
+}
+
+// Mirrors end up in nvm shell commands, so only plain http(s) URLs are accepted
+fn validate_mirror(url: &str) -> Result<String, String> {
+    let url = url.trim().trim_end_matches('/');
+    let parsed = reqwest::Url::parse(url).map_err(|e| format!("Invalid mirror URL: {}", e))?;
+    if parsed.scheme() != "http" && parsed.scheme() != "https" {
+        return Err("Mirror URL must start with http:// or https://".to_string());
+    }
+    if url.chars().any(|c| c.is_whitespace() || "\"'`$&|;<>^%!()".contains(c)) {
+        return Err("Mirror URL contains unsupported characters".to_string());
+    }
+    Ok(url.to_string())
+}
+
+// The dist folder name and archive file name for this machine, e.g.
+// ("node-v20.11.1-linux-x64", "node-v20.11.1-linux-x64.tar.xz")
+fn asset_name(version: &str) -> Result<(String, String), String> {
+    let os = match std::env::consts::OS {
+        "windows" => "win",
+        "macos" => "darwin",
+        "linux" => "linux",
+        other => return Err(format!("No Node.js builds for {}", other)),
+    };
+    let arch = match std::env::consts::ARCH {
+        "x86_64" => "x64",
+        "aarch64" => "arm64",
+        "x86" => "x86",
+        "arm" => "armv7l",
+        "powerpc64" => "ppc64le",
+        "s390x" => "s390x",
+        other => return Err(format!("No Node.js builds for {}", other)),
+    };
+    let ext = if cfg!(target_os = "windows") { "zip" } else { "tar.xz" };
+    let folder = format!("node-{}-{}-{}", version, os, arch);
+    let file = format!("{}.{}", folder, ext);
+    Ok((folder, file))
+}
+
+fn expected_checksum(http: &HttpClient, base_url: &str, version: &str, file: &str) -> Result<String, String> {
+    let url = format!("{}/{}/SHASUMS256.txt", base_url, version);
+    let response = http.get(&url)?;
+    if !response.status().is_success() {
+        return Err(format!("Failed to fetch {}: HTTP {}", url, response.status()));
+    }
+    let body = response.text().map_err(|e| e.to_string())?;
+
+    body.lines()
+        .filter_map(|line| line.split_once("  "))
+        .find(|(_, name)| name.trim() == file)
+        .map(|(sum, _)| sum.trim().to_lowercase())
+        .ok_or_else(|| format!("{} is not listed in SHASUMS256.txt", file))
+}
+
+fn parse_index(content: &str) -> Result<Vec<RemoteNodeVersion>, String> {
+    let entries: Vec<IndexEntry> = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    Ok(entries
+        .into_iter()
+        .map(|e| RemoteNodeVersion {
+            version: e.version,
+            date: e.date,
+            npm: e.npm,
+            v8: e.v8,
+            lts: e.lts.as_str().map(|s| s.to_string()),
+            security: e.security,
+        })
+        .collect())
+}
+
+// One cache file per mirror, so switching mirrors never serves another mirror's index
+fn index_cache_path(base_url: &str) -> Option<PathBuf> {
+    let key = format!("{:x}", Sha256::digest(base_url.trim_end_matches('/')));
+    managed_root().map(|r| r.join("cache").join(format!("index-{}.json", &key[..16])))
+}
+
+// Returns the release index and whether it was served from the cache after a network failure.
+// A fresh cache is used without a request unless `refresh` is set.
+pub fn fetch_index(base_url: &str, refresh: bool) -> Result<(Vec<RemoteNodeVersion>, bool), String> {
+    let cache = index_cache_path(base_url);
+    let cached = cache.as_ref().and_then(|p| {
+        let age = fs::metadata(p).and_then(|m| m.modified()).ok()?;
+        Some((fs::read_to_string(p).ok()?, SystemTime::now().duration_since(age).unwrap_or_default()))
+    });
+
+    if let Some((content, age)) = &cached {
+        if !refresh && *age < INDEX_MAX_AGE {
+            if let Ok(versions) = parse_index(content) {
+                return Ok((versions, false));
+            }
+        }
+    }
+
+    let url = format!("{}/index.json", base_url.trim_end_matches('/'));
+    let fetched = HttpClient::new()
+        .and_then(|http| http.get(&url))
+        .and_then(|r| r.error_for_status().and_then(|r| r.text()).map_err(|e| e.to_string()))
+        .and_then(|content| parse_index(&content).map(|v| (content, v)));
+
+    match fetched {
+        Ok((content, versions)) => {
+            if let Some(path) = &cache {
+                if let Some(parent) = path.parent() {
+                    let _ = fs::create_dir_all(parent);
+                }
+                let _ = fs::write(path, content);
+            }
+            Ok((versions, false))
+        }
+        Err(e) => match cached.and_then(|(content, _)| parse_index(&content).ok()) {
+            Some(versions) => Ok((versions, true)),
+            None => Err(format!("Failed to fetch {}: {}", url, e)),
+        },
+    }
+}
+
+// Exact release for a version spec, e.g. "lts/iron" -> "v20.19.0"; full versions skip the index
+pub fn resolve_remote(base_url: &str, input: &str) -> Result<String, String> {
+    if let Some(v) = Version::parse(input) {
+        return Ok(format!("v{}", v));
+    }
+    let spec = NodeSpec::parse(input).ok_or_else(|| format!("Invalid Node.js version: {}", input))?;
+    let (versions, _) = fetch_index(base_url, false)?;
+    spec.resolve(versions.iter().map(|v| (v.version.as_str(), v.lts.as_deref())))
+        .map(|v| format!("v{}", v))
+        .ok_or_else(|| format!("No Node.js release matches {}", input))
+}
+
+// LTS codenames by version ("v20.11.1" -> "Iron"), since installs carry no release metadata
+pub fn lts_codenames() -> HashMap<String, String> {
+    fetch_index(&dist_url(), false)
+        .map(|(versions, _)| versions.into_iter().filter_map(|v| Some((v.version, v.lts?))).collect())
+        .unwrap_or_default()
+}
+
+// Streams the download to disk, hashing as it goes, and returns the hex SHA-256
+fn download(http: &HttpClient, url: &str, dest: &Path, on_progress: &mut dyn FnMut(u64, Option<u64>)) -> Result<String, String> {
+    let mut response = http.get(url)?;
+    if !response.status().is_success() {
+        return Err(format!("Failed to download {}: HTTP {}", url, response.status()));
+    }
+    let total = response.content_length();
+
+    let mut file = File::create(dest).map_err(|e| e.to_string())?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0; 16384];
+    let mut downloaded: u64 = 0;
+
+    loop {
+        let bytes_read = response.read(&mut buffer).map_err(|e| e.to_string())?;
+        if bytes_read == 0 {
+            break;
+        }
+        file.write_all(&buffer[..bytes_read]).map_err(|e| e.to_string())?;
+        hasher.update(&buffer[..bytes_read]);
+        downloaded += bytes_read as u64;
+        on_progress(downloaded, total);
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn extract(archive: &Path, dest: &Path) -> Result<(), String> {
+    let file = File::open(archive).map_err(|e| e.to_string())?;
+    let mut tar = tar::Archive::new(xz2::read::XzDecoder::new(file));
+    tar.set_preserve_permissions(true);
+    tar.unpack(dest).map_err(|e| e.to_string())
+}
+
+#[cfg(target_os = "windows")]
+fn extract(archive: &Path, dest: &Path) -> Result<(), String> {
+    let file = File::open(archive).map_err(|e| e.to_string())?;
+    let mut zip = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+    zip.extract(dest).map_err(|e| e.to_string())
+}
+
+// Downloads, verifies and unpacks a release into <versions_dir>/<version>.
+// Nothing is left behind in versions_dir unless every step succeeds.
+pub fn install(
+    base_url: &str,
+    version: &str,
+    versions_dir: &Path,
+    on_progress: &mut dyn FnMut(&str, u64, Option<u64>),
+) -> Result<PathBuf, String> {
+    let version = match Version::parse(version) {
+        Some(v) => format!("v{}", v),
+        None => return Err(format!("Invalid Node.js version: {}", version)),
+    };
+    let base_url = base_url.trim_end_matches('/');
+    let target = versions_dir.join(&version);
+    if target.exists() {
+        return Err(format!("Node.js {} is already installed", version));
+    }
+
+    let (folder, file) = asset_name(&version)?;
+    let http = HttpClient::new()?;
+    let expected = expected_checksum(&http, base_url, &version, &file)?;
+
+    fs::create_dir_all(versions_dir).map_err(|e| e.to_string())?;
+    let archive = versions_dir.join(format!(".download-{}", file));
+    let staging = versions_dir.join(format!(".extract-{}", version));
+
+    let result = (|| {
+        let url = format!("{}/{}/{}", base_url, version, file);
+        let actual = download(&http, &url, &archive, &mut |done, total| on_progress("download", done, total))?;
+
+        on_progress("verify", 0, None);
+        if actual != expected {
+            return Err(format!("Checksum mismatch for {}: expected {}, got {}", file, expected, actual));
+        }
+
+        on_progress("extract", 0, None);
+        let _ = fs::remove_dir_all(&staging);
+        extract(&archive, &staging)?;
+        fs::rename(staging.join(&folder), &target).map_err(|e| e.to_string())?;
+        Ok(target.clone())
+    })();
+
+    let _ = fs::remove_file(&archive);
+    let _ = fs::remove_dir_all(&staging);
+    if result.is_ok() {
+        on_progress("done", 0, None);
+    }
+    result
+}
+
+#[command]
+pub async fn install_node_native(app: AppHandle, version: String, base_url: Option<String>) -> Result<String, String> {
+    let versions_dir = versions_dir().ok_or("Could not determine the data directory")?;
+    let base_url = base_url.unwrap_or_else(dist_url);
+
+    let path = tauri::async_runtime::spawn_blocking(move || {
+        let version = resolve_remote(&base_url, &version)?;
+        let mut last_percentage = 0;
+        install(&base_url, &version, &versions_dir, &mut |stage, downloaded, total| {
+            if stage == "download" {
+                // Only emit when the whole percentage changes to avoid flooding the frontend
+                let percentage = total.filter(|t| *t > 0).map_or(0, |t| downloaded * 100 / t);
+                if percentage == last_percentage && downloaded > 0 {
+                    return;
+                }
+                last_percentage = percentage;
+            }
+            let _ = app.emit(
+                "node-install-progress",
+                InstallProgress { version: &version, stage, downloaded, total },
+            );
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    Ok(path.to_string_lossy().to_string())
+}
+
+#[command]
+pub async fn get_remote_node_versions(
+    base_url: Option<String>,
+    major: Option<u64>,
+    lts_only: Option<bool>,
+    refresh: Option<bool>,
+) -> Result<RemoteVersionList, String> {
+    let base_url = base_url.unwrap_or_else(dist_url);
+
+    let (versions, offline) = tauri::async_runtime::spawn_blocking(move || fetch_index(&base_url, refresh.unwrap_or(false)))
+        .await
+        .map_err(|e| e.to_string())??;
+
+    let versions = versions
+        .into_iter()
+        .filter(|v| !lts_only.unwrap_or(false) || v.lts.is_some())
+        .filter(|v| major.is_none_or(|m| Version::parse(&v.version).is_some_and(|p| p.major == m)))
+        .collect();
+
+    Ok(RemoteVersionList { versions, offline })
+}
+
+#[command]
+pub fn get_node_mirror() -> Option<String> {
+    settings::load().node_mirror
+}
+
+#[command]
+pub fn set_node_mirror(url: Option<String>) -> Result<(), String> {
+    let mut current = settings::load();
+    current.node_mirror = match url.filter(|u| !u.trim().is_empty()) {
+        Some(url) => Some(validate_mirror(&url)?),
+        None => None,
+    };
+    settings::save(&current)
+}
+
+// Checks a mirror (the configured one by default) by requesting its index.json
+#[command]
+pub async fn test_node_mirror(url: Option<String>) -> Result<MirrorTestResult, String> {
+    let url = match url {
+        Some(url) => validate_mirror(&url)?,
+        None => dist_url(),
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        // A quick probe through the configured proxy, without the usual retries
+        let mut config = settings::load().http;
+        config.read_timeout_secs = 10;
+        config.retries = 0;
+        let http = HttpClient::from_settings(&config)?;
+
+        let started = Instant::now();
+        let result = http.client.head(format!("{}/index.json", url)).send();
+        let latency_ms = started.elapsed().as_millis() as u64;
+
+        Ok(match result {
+            Ok(response) => MirrorTestResult {
+                ok: response.status().is_success(),
+                status: Some(response.status().as_u16()),
+                latency_ms,
+                error: None,
+                url,
+            },
+            Err(e) => MirrorTestResult {
+                ok: false,
+                status: None,
+                latency_ms,
+                error: Some(e.to_string()),
+                url,
+            },
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(all(test, not(target_os = "windows")))]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+    use std::thread;
+
+    // Minimal HTTP/1.1 stand-in for a dist mirror, serving fixed paths until the test exits
+    fn serve(files: HashMap<String, Vec<u8>>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let mut stream = stream;
+                let mut request_line = String::new();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                reader.read_line(&mut request_line).unwrap();
+                loop {
+                    let mut header = String::new();
+                    if reader.read_line(&mut header).unwrap() == 0 || header == "\r\n" {
+                        break;
+                    }
+                }
+                let path = request_line.split_whitespace().nth(1).unwrap_or("/");
+                let response = match files.get(path) {
+                    Some(body) => {
+                        let mut r = format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n", body.len()).into_bytes();
+                        r.extend_from_slice(body);
+                        r
+                    }
+                    None => b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_vec(),
+                };
+                let _ = stream.write_all(&response);
+            }
+        });
+        format!("http://{}/dist", addr)
+    }
+
+    fn fake_release(version: &str) -> (String, Vec<u8>) {
+        let (folder, file) = asset_name(version).unwrap();
+        let mut tar = tar::Builder::new(xz2::write::XzEncoder::new(Vec::new(), 6));
+        let script = b"#!/bin/sh\necho v99.0.0\n";
+        let mut header = tar::Header::new_gnu();
+        header.set_size(script.len() as u64);
+        header.set_mode(0o755);
+        header.set_cksum();
+        tar.append_data(&mut header, format!("{}/bin/node", folder), &script[..]).unwrap();
+        (file, tar.into_inner().unwrap().finish().unwrap())
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("fp-node-dist-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn installs_verified_release() {
+        let (file, archive) = fake_release("v99.0.0");
+        let sums = format!("{:x}  {}\n", Sha256::digest(&archive), file);
+        let base = serve(HashMap::from([
+            (format!("/dist/v99.0.0/{}", file), archive),
+            ("/dist/v99.0.0/SHASUMS256.txt".to_string(), sums.into_bytes()),
+        ]));
+
+        let dir = temp_dir("ok");
+        let path = install(&base, "99.0.0", &dir, &mut |_, _, _| {}).unwrap();
+
+        assert_eq!(path, dir.join("v99.0.0"));
+        assert!(path.join("bin").join("node").is_file());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn rejects_checksum_mismatch() {
+        let (file, archive) = fake_release("v99.0.0");
+        let sums = format!("{:x}  {}\n", Sha256::digest(b"something else"), file);
+        let base = serve(HashMap::from([
+            (format!("/dist/v99.0.0/{}", file), archive),
+            ("/dist/v99.0.0/SHASUMS256.txt".to_string(), sums.into_bytes()),
+        ]));
+
+        let dir = temp_dir("tampered");
+        let err = install(&base, "v99.0.0", &dir, &mut |_, _, _| {}).unwrap_err();
+
+        assert!(err.contains("Checksum mismatch"));
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
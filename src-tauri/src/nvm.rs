@@ -1,241 +1,555 @@
-use tauri::command;
-use std::env;
+use tauri::{command, AppHandle, Emitter, State};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs;
+use std::io::{BufReader, Read};
 use std::path::Path;
 use serde::Serialize;
-use std::process::Command;
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crate::config;
+use crate::discovery;
+use crate::disk;
+use crate::globals;
+use crate::node_dist;
+use crate::runner::{self, ProcessState};
+use crate::settings;
+use crate::shim;
+use crate::version::Version;
 
 #[cfg(target_os = "windows")]
 use std::os::windows::process::CommandExt;
 
 #[cfg(target_os = "windows")]
-#[allow(dead_code)]
 const CREATE_NO_WINDOW: u32 = 0x08000000;
 
+// How long a forced uninstall waits for the scripts it stopped to exit
+const STOP_TIMEOUT: Duration = Duration::from_secs(10);
+
 #[cfg(target_os = "windows")]
-const CREATE_NEW_CONSOLE: u32 = 0x00000010;
+const NODE_EXE: &str = "node.exe";
+#[cfg(not(target_os = "windows"))]
+const NODE_EXE: &str = "node";
 
 #[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct NodeVersion {
     version: String,
     path: String,
-    source: String, // "nvm" or "custom"
+    source: String, // "nvm", "managed", "fnm", "volta", "asdf", "n", "nvs", "system" or "custom"
+    npm_version: Option<String>,
+    // "x64", "arm64", ...; `emulated` is set when it differs from the host, e.g. x64 under Rosetta
+    arch: Option<String>,
+    emulated: bool,
+    // Bytes on disk, not measured for system installs that share their prefix with other software
+    size: Option<u64>,
+    is_default: bool,
+    // Names of saved projects configured to use this version
+    projects: Vec<String>,
 }
 
-#[command]
-pub async fn install_node(version: String) -> Result<String, String> {
-    #[cfg(target_os = "windows")]
-    {
-        // Use cmd /C to wrap nvm command to satisfy "Terminal Only" requirement of nvm-windows
-        // Use CREATE_NEW_CONSOLE to show the window so user can see progress and bypass terminal check
-        // We add "& pause" so the window stays open regardless of success/failure
-        // allowing the user to read the output/errors.
-        let mut cmd = Command::new("cmd");
-        let command_str = format!("title Installing Node {0} & echo Executing: nvm install {0} & nvm install {0} & pause", version);
-        cmd.arg("/C").arg(&command_str);
-        
-        cmd.creation_flags(CREATE_NEW_CONSOLE);
-
-        // Use status() instead of output() to let stdout/stderr go to the new console window
-        let _ = cmd.status().map_err(|e| e.to_string())?;
-
-        Ok("Installation process finished".to_string())
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockingProcess {
+    // Run id, "<project id>:<script>"
+    id: String,
+    path: String,
+}
+
+// Outcome of install_node: the exact version a spec such as "lts/*" or "^20.1" resolved to
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallResult {
+    version: String,
+    message: String,
+}
+
+// Outcome of uninstall_node: a refusal lists what still uses the version, a forced
+// uninstall lists what it overrode
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UninstallResult {
+    uninstalled: bool,
+    message: String,
+    projects: Vec<String>,
+    processes: Vec<BlockingProcess>,
+}
+
+pub struct NvmState {
+    // Operation id (e.g. "install:v20.11.1") -> pid of the running nvm process
+    pub operations: Arc<Mutex<HashMap<String, u32>>>,
+    pub cancelled: Arc<Mutex<HashSet<String>>>,
+}
+
+impl NvmState {
+    pub fn new() -> Self {
+        Self {
+            operations: Arc::new(Mutex::new(HashMap::new())),
+            cancelled: Arc::new(Mutex::new(HashSet::new())),
+        }
     }
+}
 
-    #[cfg(target_os = "macos")]
-    {
-        let script = format!("source ~/.nvm/nvm.sh && nvm install {}", version);
-        let apple_script = format!("tell application \"Terminal\" to do script \"{}\"", script);
-        
-        let _ = Command::new("osascript")
-            .arg("-e")
-            .arg(apple_script)
-            .status()
-            .map_err(|e| e.to_string())?;
-            
-        Ok("Started in Terminal".to_string())
+// Versions reach cmd.exe on Windows, so only allow what nvm itself accepts
+fn validate_version_arg(version: &str) -> Result<(), String> {
+    let valid = !version.is_empty()
+        && version.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '/' | '*'));
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("Invalid Node.js version: {}", version))
     }
+}
 
-    #[cfg(target_os = "linux")]
-    {
-        // Try common terminal emulators
-        let script = format!("source ~/.nvm/nvm.sh && nvm install {} && read -p 'Press enter to close'", version);
-        let terminals = vec![
-            ("gnome-terminal", vec!["--", "bash", "-c", &script]),
-            ("x-terminal-emulator", vec!["-e", "bash", "-c", &script]),
-            ("konsole", vec!["-e", "bash", "-c", &script]),
-            ("xfce4-terminal", vec!["-e", "bash", "-c", &script]),
-            ("xterm", vec!["-e", "bash", "-c", &script]),
-        ];
-
-        for (term, args) in terminals {
-            if Command::new(term).args(args).spawn().is_ok() {
-                return Ok("Started in Terminal".to_string());
-            }
-        }
+// Last "NN.N%" on a line, as printed by curl's progress bar (nvm) or nvm-windows
+fn parse_percentage(line: &str) -> Option<f64> {
+    let end = line.rfind('%')?;
+    let start = line[..end]
+        .rfind(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .map_or(0, |i| i + 1);
+    line[start..end].parse().ok().filter(|p| (0.0..=100.0).contains(p))
+}
 
-        // Fallback to background execution
-        let _ = Command::new("bash")
-            .arg("-c")
-            .arg(format!("source ~/.nvm/nvm.sh && nvm install {}", version))
-            .status()
-            .map_err(|e| e.to_string())?;
-            
-        Ok("Executed in background".to_string())
+// nvm-windows runs hidden with its output piped back, like nvm elsewhere. It has no
+// per-run mirror option, so run_nvm points settings.txt at the mirror instead
+#[cfg(target_os = "windows")]
+fn nvm_command(args: &[&str], _mirror: Option<&str>) -> Result<Command, String> {
+    let mut cmd = Command::new("cmd");
+    cmd.arg("/C").arg("nvm").args(args).creation_flags(CREATE_NO_WINDOW);
+    Ok(cmd)
+}
+
+// The user's own node_mirror line from nvm-windows' settings.txt, empty when unset
+#[cfg(target_os = "windows")]
+fn nvm_windows_mirror() -> String {
+    std::env::var_os("NVM_HOME")
+        .and_then(|home| fs::read_to_string(Path::new(&home).join("settings.txt")).ok())
+        .and_then(|settings| {
+            settings
+                .lines()
+                .find_map(|l| l.trim().strip_prefix("node_mirror:").map(|m| m.trim().to_string()))
+        })
+        .unwrap_or_default()
+}
+
+// `nvm node_mirror` with no URL goes back to nodejs.org. Runs nvm directly so the URL
+// never passes through cmd.exe
+#[cfg(target_os = "windows")]
+fn set_nvm_windows_mirror(mirror: &str) -> Result<(), String> {
+    let mut cmd = Command::new("nvm");
+    cmd.arg("node_mirror");
+    if !mirror.is_empty() {
+        cmd.arg(mirror);
+    }
+    let status = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .creation_flags(CREATE_NO_WINDOW)
+        .status()
+        .map_err(|e| e.to_string())?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("nvm node_mirror failed with {}", status))
     }
 }
 
-#[command]
-pub async fn uninstall_node(version: String) -> Result<String, String> {
+#[cfg(not(target_os = "windows"))]
+fn nvm_command(args: &[&str], mirror: Option<&str>) -> Result<Command, String> {
+    use std::os::unix::process::CommandExt;
+
+    let home = std::env::var("HOME").unwrap_or_default();
+    let nvm_dir = std::env::var("NVM_DIR").unwrap_or_else(|_| format!("{}/.nvm", home));
+    if !Path::new(&nvm_dir).join("nvm.sh").exists() {
+        return Err("nvm is not installed, use the built-in installer instead".to_string());
+    }
+
+    // Arguments go in as positional parameters so they are never parsed by the shell
+    let mut cmd = Command::new("bash");
+    cmd.arg("-c")
+        .arg("source \"$NVM_DIR/nvm.sh\" && nvm \"$@\"")
+        .arg("nvm")
+        .args(args)
+        .env("NVM_DIR", &nvm_dir)
+        .env("TERM", "dumb")
+        // Own process group, so cancelling also stops the curl/tar children
+        .process_group(0);
+    if let Some(mirror) = mirror {
+        cmd.env("NVM_NODEJS_ORG_MIRROR", mirror);
+    }
+    Ok(cmd)
+}
+
+fn kill_tree(pid: u32) {
     #[cfg(target_os = "windows")]
     {
-        let mut cmd = Command::new("cmd");
-        let command_str = format!("title Uninstalling Node {0} & echo Executing: nvm uninstall {0} & nvm uninstall {0} & pause", version);
-        cmd.arg("/C").arg(&command_str);
-        
-        cmd.creation_flags(CREATE_NEW_CONSOLE);
+        let _ = Command::new("taskkill")
+            .args(["/PID", &pid.to_string(), "/F", "/T"])
+            .creation_flags(CREATE_NO_WINDOW)
+            .spawn();
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = Command::new("kill")
+            .args(["-TERM", "--", &format!("-{}", pid)])
+            .spawn();
+    }
+}
+
+// Runs nvm as a tracked child process, streaming its output as "nvm-output" events and
+// download progress as "nvm-progress", and fails unless nvm exits successfully
+async fn run_nvm(app: AppHandle, state: &NvmState, id: String, args: Vec<String>, mirror: Option<String>) -> Result<String, String> {
+    let operations = state.operations.clone();
+    let cancelled = state.cancelled.clone();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        // nvm-windows downloads from the mirror in settings.txt, so it is switched for this
+        // run only and the user's value put back afterwards
+        #[cfg(target_os = "windows")]
+        let previous_mirror = match mirror.as_deref() {
+            Some(mirror) => {
+                let previous = nvm_windows_mirror();
+                set_nvm_windows_mirror(mirror)?;
+                Some(previous)
+            }
+            None => None,
+        };
+
+        let result = (|| {
+            let arg_refs: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+            let mut cmd = nvm_command(&arg_refs, mirror.as_deref())?;
+            cmd.stdin(Stdio::null()).stdout(Stdio::piped()).stderr(Stdio::piped());
+            settings::load().http.apply_proxy_env(&mut cmd);
+
+            let mut lock = operations.lock().map_err(|e| e.to_string())?;
+            if lock.contains_key(&id) {
+                return Err("Operation is already running".to_string());
+            }
+            let child = cmd.spawn().map_err(|e| e.to_string())?;
+            lock.insert(id.clone(), child.id());
+            drop(lock);
+
+            wait_for_nvm(&app, &id, child, &operations, &cancelled)
+        })();
+
+        #[cfg(target_os = "windows")]
+        if let Some(previous) = previous_mirror {
+            if let Err(e) = set_nvm_windows_mirror(&previous) {
+                println!("Failed to restore the nvm mirror: {}", e);
+            }
+        }
+        result
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+fn wait_for_nvm(
+    app: &AppHandle,
+    id: &str,
+    mut child: Child,
+    operations: &Mutex<HashMap<String, u32>>,
+    cancelled: &Mutex<HashSet<String>>,
+) -> Result<String, String> {
+    let tail = Arc::new(Mutex::new(VecDeque::with_capacity(20)));
+
+    let readers: Vec<_> = [
+        ("stdout", child.stdout.take().map(|s| Box::new(s) as Box<dyn Read + Send>)),
+        ("stderr", child.stderr.take().map(|s| Box::new(s) as Box<dyn Read + Send>)),
+    ]
+    .into_iter()
+    .filter_map(|(stream, reader)| reader.map(|r| (stream, r)))
+    .map(|(stream, reader)| {
+        let app = app.clone();
+        let id = id.to_string();
+        let tail = tail.clone();
+        thread::spawn(move || {
+            let mut reader = BufReader::new(reader);
+            let mut buf = Vec::new();
+            // curl redraws its progress bar with '\r', so treat it as a line break too
+            loop {
+                buf.clear();
+                let mut byte = [0u8; 1];
+                loop {
+                    match reader.read(&mut byte) {
+                        Ok(1) if byte[0] != b'\n' && byte[0] != b'\r' => buf.push(byte[0]),
+                        Ok(1) => break,
+                        _ => {
+                            if buf.is_empty() {
+                                return;
+                            }
+                            break;
+                        }
+                    }
+                }
+                let line = String::from_utf8_lossy(&buf).trim().to_string();
+                if line.is_empty() {
+                    continue;
+                }
+                if let Some(percentage) = parse_percentage(&line) {
+                    let _ = app.emit("nvm-progress", serde_json::json!({ "id": id, "percentage": percentage }));
+                    continue;
+                }
+                let _ = app.emit("nvm-output", serde_json::json!({ "id": id, "type": stream, "data": line }));
+                if let Ok(mut tail) = tail.lock() {
+                    if tail.len() >= 20 {
+                        tail.pop_front();
+                    }
+                    tail.push_back(line);
+                }
+            }
+        })
+    })
+    .collect();
 
-        let _ = cmd.status().map_err(|e| e.to_string())?;
+    let status = child.wait();
+    for reader in readers {
+        let _ = reader.join();
+    }
 
-        Ok("Uninstall process finished".to_string())
+    if let Ok(mut lock) = operations.lock() {
+        lock.remove(id);
     }
+    let was_cancelled = cancelled.lock().map(|mut c| c.remove(id)).unwrap_or(false);
 
-    #[cfg(target_os = "macos")]
-    {
-        let script = format!("source ~/.nvm/nvm.sh && nvm uninstall {}", version);
-        let apple_script = format!("tell application \"Terminal\" to do script \"{}\"", script);
-        
-        let _ = Command::new("osascript")
-            .arg("-e")
-            .arg(apple_script)
-            .status()
-            .map_err(|e| e.to_string())?;
-            
-        Ok("Started in Terminal".to_string())
+    let output: Vec<String> = tail.lock().map(|t| t.iter().cloned().collect()).unwrap_or_default();
+    let _ = app.emit("nvm-exit", serde_json::json!({ "id": id, "success": matches!(&status, Ok(s) if s.success()) }));
+
+    if was_cancelled {
+        return Err("Cancelled".to_string());
     }
+    let status = status.map_err(|e| e.to_string())?;
+    // nvm-windows reports many failures on stdout with a zero exit code
+    let reported_error = output.iter().any(|l| l.starts_with("ERROR") || l.contains("error installing"));
+    if status.success() && !reported_error {
+        Ok(output.last().cloned().unwrap_or_default())
+    } else if output.is_empty() {
+        Err(format!("nvm failed with {}", status))
+    } else {
+        Err(output.join("\n"))
+    }
+}
 
-    #[cfg(target_os = "linux")]
-    {
-        let _ = Command::new("bash")
-            .arg("-c")
-            .arg(format!("source ~/.nvm/nvm.sh && nvm uninstall {}", version))
-            .status()
-            .map_err(|e| e.to_string())?;
-            
-        Ok("Executed in background".to_string())
+#[command]
+pub fn cancel_nvm_operation(state: State<'_, NvmState>, id: String) -> Result<(), String> {
+    let lock = state.operations.lock().map_err(|e| e.to_string())?;
+    if let Some(pid) = lock.get(&id) {
+        if let Ok(mut cancelled) = state.cancelled.lock() {
+            cancelled.insert(id.clone());
+        }
+        kill_tree(*pid);
     }
+    Ok(())
 }
 
 #[command]
-pub async fn use_node(version: String) -> Result<String, String> {
+pub async fn install_node(app: AppHandle, state: State<'_, NvmState>, version: String) -> Result<InstallResult, String> {
+    validate_version_arg(&version)?;
+    let mirror = node_dist::dist_url();
+
+    // Resolve "lts/*", "18" and the like ourselves, since nvm and nvm-windows disagree on aliases
+    let base_url = mirror.clone();
+    let input = version.clone();
+    let resolved = tauri::async_runtime::spawn_blocking(move || node_dist::resolve_remote(&base_url, &input))
+        .await
+        .map_err(|e| e.to_string())??;
+    let resolved = resolved.trim_start_matches('v').to_string();
+
+    let message = run_nvm(app, &state, format!("install:{}", version), vec!["install".to_string(), resolved.clone()], Some(mirror)).await?;
+
+    // nvm-windows can exit cleanly without installing anything, e.g. when the download fails
     #[cfg(target_os = "windows")]
     {
-        let mut cmd = Command::new("cmd");
-        let command_str = format!("title Switching to Node {0} & echo Executing: nvm use {0} & nvm use {0} & pause", version);
-        cmd.arg("/C").arg(&command_str);
-        
-        cmd.creation_flags(CREATE_NEW_CONSOLE);
+        let wanted = resolved.clone();
+        let installed = tauri::async_runtime::spawn_blocking(move || discovery::resolve_installed(&wanted))
+            .await
+            .map_err(|e| e.to_string())??;
+        if installed.is_none() {
+            return Err(format!("nvm did not install Node {}", resolved));
+        }
+    }
+    Ok(InstallResult { version: format!("v{}", resolved), message })
+}
 
-        let _ = cmd.status().map_err(|e| e.to_string())?;
+#[command]
+pub async fn uninstall_node(
+    app: AppHandle,
+    state: State<'_, NvmState>,
+    process_state: State<'_, ProcessState>,
+    version: String,
+    force: Option<bool>,
+) -> Result<UninstallResult, String> {
+    // Parsed before anything else: the version ends up in a path that is deleted recursively
+    let target = Version::parse(&version).ok_or_else(|| format!("Invalid Node.js version: {}", version))?;
+    let version = target.to_string();
 
-        Ok("Switch process finished".to_string())
-    }
+    let wanted = target.clone();
+    let bin_dirs = tauri::async_runtime::spawn_blocking(move || {
+        discovery::find_all()
+            .into_iter()
+            .filter(|f| Version::parse(&f.version).as_ref() == Some(&wanted))
+            .map(|f| f.bin_dir)
+            .collect::<Vec<_>>()
+    })
+    .await
+    .map_err(|e| e.to_string())?;
 
-    #[cfg(target_os = "macos")]
-    {
-        let script = format!("source ~/.nvm/nvm.sh && nvm use {}", version);
-        let apple_script = format!("tell application \"Terminal\" to do script \"{}\"", script);
-        
-        let _ = Command::new("osascript")
-            .arg("-e")
-            .arg(apple_script)
-            .status()
-            .map_err(|e| e.to_string())?;
-            
-        Ok("Started in Terminal".to_string())
+    let projects: Vec<String> = config::saved_projects()
+        .into_iter()
+        .filter(|p| uses_version(&p.node_version, &version))
+        .map(|p| p.name)
+        .collect();
+    let processes: Vec<BlockingProcess> = runner::processes_using_node(&process_state, &bin_dirs)
+        .into_iter()
+        .map(|(id, path)| BlockingProcess { id, path })
+        .collect();
+
+    let in_use = !projects.is_empty() || !processes.is_empty();
+    if in_use && !force.unwrap_or(false) {
+        return Ok(UninstallResult {
+            uninstalled: false,
+            message: format!("Node {} is still in use", version),
+            projects,
+            processes,
+        });
     }
 
-    #[cfg(target_os = "linux")]
-    {
-        let _ = Command::new("bash")
-            .arg("-c")
-            .arg(format!("source ~/.nvm/nvm.sh && nvm alias default {}", version))
-            .status()
-            .map_err(|e| e.to_string())?;
-            
-        Ok("Set as default".to_string())
+    if !processes.is_empty() {
+        let ids: Vec<String> = processes.iter().map(|p| p.id.clone()).collect();
+        let still_running = runner::stop_and_wait(&process_state, &ids, STOP_TIMEOUT);
+        if !still_running.is_empty() {
+            return Err(format!("Could not stop {}", still_running.join(", ")));
+        }
     }
+
+    // Versions from the built-in installer are plain directories we own
+    let managed = node_dist::versions_dir().map(|dir| dir.join(format!("v{}", version)));
+    let message = match managed {
+        Some(managed) if managed.is_dir() => {
+            fs::remove_dir_all(&managed).map_err(|e| e.to_string())?;
+            "Uninstalled".to_string()
+        }
+        _ => run_nvm(app, &state, format!("uninstall:{}", version), vec!["uninstall".to_string(), version], None).await?,
+    };
+
+    Ok(UninstallResult { uninstalled: true, message, projects, processes })
 }
 
 #[command]
-pub fn get_nvm_list() -> Result<Vec<NodeVersion>, String> {
-    let mut versions = Vec::new();
+pub async fn use_node(app: AppHandle, state: State<'_, NvmState>, version: String) -> Result<String, String> {
+    validate_version_arg(&version)?;
+
+    let input = version.clone();
+    let found = tauri::async_runtime::spawn_blocking(move || discovery::resolve_installed(&input))
+        .await
+        .map_err(|e| e.to_string())??
+        .ok_or_else(|| format!("No installed Node.js version matches {}", version))?;
+    let resolved = found.version.trim_start_matches('v').to_string();
 
+    // nvm-windows switches its global symlink, which can only point at its own installs
     #[cfg(target_os = "windows")]
     {
-        let nvm_home = env::var("NVM_HOME").map_err(|_| "NVM_HOME not set".to_string())?;
-        let path = Path::new(&nvm_home);
-        if let Ok(entries) = fs::read_dir(path) {
-            for entry in entries {
-                if let Ok(entry) = entry {
-                    let path = entry.path();
-                    if path.is_dir() {
-                        if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                            if name.starts_with("v") {
-                                versions.push(NodeVersion {
-                                    version: name.to_string(),
-                                    path: path.to_string_lossy().to_string(),
-                                    source: "nvm".to_string(),
-                                });
-                            }
-                        }
-                    }
-                }
-            }
+        if found.source != "nvm" {
+            return Err(format!("Node {} comes from {}, which nvm-windows cannot switch to", found.version, found.source));
         }
+        run_nvm(app, &state, format!("use:{}", version), vec!["use".to_string(), resolved], None).await
     }
 
+    // Elsewhere the app's own `current` link is what new shells pick up; nvm's default alias
+    // is kept in step so shells that load nvm.sh agree with it
     #[cfg(not(target_os = "windows"))]
     {
-        let home = env::var("HOME").unwrap_or_default();
-        let nvm_dir = env::var("NVM_DIR").unwrap_or_else(|_| format!("{}/.nvm", home));
-        let versions_path = Path::new(&nvm_dir).join("versions").join("node");
-        
-        if versions_path.exists() {
-             if let Ok(entries) = fs::read_dir(versions_path) {
-                for entry in entries {
-                    if let Ok(entry) = entry {
-                        let path = entry.path();
-                        if path.is_dir() {
-                            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                                if name.starts_with("v") {
-                                    versions.push(NodeVersion {
-                                        version: name.to_string(),
-                                        path: path.to_string_lossy().to_string(),
-                                        source: "nvm".to_string(),
-                                    });
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+        let bin = shim::set_default(&found.bin_dir)?;
+        if found.source == "nvm" {
+            let args = vec!["alias".to_string(), "default".to_string(), resolved];
+            run_nvm(app, &state, format!("use:{}", version), args, None).await?;
         }
+        Ok(format!("Now using Node {} from {}", found.version, bin.display()))
     }
-    
-    versions.sort_by(|a, b| {
-        let parse_version = |s: &str| -> Vec<u32> {
-            s.trim_start_matches('v')
-                .split('.')
-                .map(|p| p.parse().unwrap_or(0))
-                .collect()
-        };
-        let va = parse_version(&a.version);
-        let vb = parse_version(&b.version);
-        vb.cmp(&va) 
-    });
-    
-    Ok(versions)
+}
+
+// A project's configured Node version names an install when both parse to the same version,
+// so "v18.20.2" and "18.20.2" agree for the list badge and the uninstall guard alike
+fn uses_version(configured: &str, installed: &str) -> bool {
+    Version::parse(configured).is_some_and(|v| Version::parse(installed) == Some(v))
+}
+
+#[command]
+pub async fn get_nvm_list() -> Result<Vec<NodeVersion>, String> {
+    tauri::async_runtime::spawn_blocking(list_versions)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+fn list_versions() -> Vec<NodeVersion> {
+    let found = discovery::find_all();
+    let projects = config::saved_projects();
+    let default_binary = default_node_binary();
+
+    // Sizing walks every file of every install, so measure them side by side
+    let sizes: Vec<_> = found
+        .iter()
+        .map(|f| {
+            let prefix = (f.source != "system").then(|| globals::prefix(&f.bin_dir.to_string_lossy()).ok()).flatten();
+            thread::spawn(move || prefix.map(|p| disk::dir_size(&p)))
+        })
+        .collect();
+
+    let mut versions: Vec<NodeVersion> = found
+        .into_iter()
+        .zip(sizes)
+        .map(|(f, size)| {
+            let path = f.bin_dir.to_string_lossy().to_string();
+            let binary = f.bin_dir.join(NODE_EXE);
+            let arch = discovery::binary_arch(&binary);
+            NodeVersion {
+                npm_version: npm_version(&path),
+                emulated: arch.is_some_and(|a| a != "universal" && a != discovery::host_arch()),
+                arch: arch.map(|a| a.to_string()),
+                size: size.join().ok().flatten(),
+                is_default: default_binary.is_some() && fs::canonicalize(&binary).ok() == default_binary,
+                projects: projects
+                    .iter()
+                    .filter(|p| uses_version(&p.node_version, &f.version))
+                    .map(|p| p.name.clone())
+                    .collect(),
+                version: f.version,
+                path,
+                source: f.source.to_string(),
+            }
+        })
+        .collect();
+
+    // Newest first; anything unparseable sorts last
+    versions.sort_by(|a, b| Version::parse(&b.version).cmp(&Version::parse(&a.version)));
+    versions
+}
+
+fn npm_version(node_path: &str) -> Option<String> {
+    let prefix = globals::prefix(node_path).ok()?;
+    let manifest = fs::read_to_string(globals::global_modules(&prefix).join("npm").join("package.json")).ok()?;
+    let manifest: serde_json::Value = serde_json::from_str(&manifest).ok()?;
+    manifest["version"].as_str().map(|v| v.to_string())
+}
+
+// The binary new shells run: the target of the managed default link (or nvm-windows'
+// symlink), otherwise whatever node is first on PATH
+fn default_node_binary() -> Option<std::path::PathBuf> {
+    let binary = match shim::default_target() {
+        Some(target) if cfg!(target_os = "windows") => target.join(NODE_EXE),
+        Some(target) => target.join("bin").join(NODE_EXE),
+        None => {
+            let dir = get_system_node_path();
+            if dir == "System Default" {
+                return None;
+            }
+            Path::new(&dir).join(NODE_EXE)
+        }
+    };
+    fs::canonicalize(binary).ok()
 }
 
 #[command]
@@ -334,3 +648,16 @@ fn strip_executable_name(path_str: String) -> String {
     }
     path_str
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn project_versions_match_with_or_without_prefix() {
+        assert!(uses_version("v18.20.2", "18.20.2"));
+        assert!(uses_version("18.20.2", "v18.20.2"));
+        assert!(!uses_version("18.20.1", "v18.20.2"));
+        assert!(!uses_version("18", "v18.20.2"));
+    }
+}
@@ -1,24 +1,41 @@
 use tauri::command;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::Path;
 
+use crate::discovery;
+use crate::node_dist;
+use crate::version::NodeSpec;
+
 #[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ProjectInfo {
     name: String,
     scripts: Vec<String>,
     path: String,
+    script_commands: BTreeMap<String, String>,
+    version: Option<String>,
+    description: Option<String>,
+    dependency_count: usize,
+    dev_dependency_count: usize,
+    framework: Option<String>,
+    build_tool: Option<String>,
 }
 
 #[derive(Deserialize)]
-struct PackageJson {
-    name: Option<String>,
-    scripts: Option<std::collections::HashMap<String, String>>,
+pub(crate) struct PackageJson {
+    pub(crate) name: Option<String>,
+    pub(crate) version: Option<String>,
+    pub(crate) description: Option<String>,
+    pub(crate) scripts: Option<HashMap<String, String>>,
+    pub(crate) dependencies: Option<HashMap<String, String>>,
+    #[serde(rename = "devDependencies")]
+    pub(crate) dev_dependencies: Option<HashMap<String, String>>,
+    pub(crate) engines: Option<HashMap<String, String>>,
 }
 
-#[command]
-pub fn scan_project(path: String) -> Result<ProjectInfo, String> {
-    let project_path = Path::new(&path);
+pub(crate) fn read_package_json(project_path: &Path) -> Result<PackageJson, String> {
     let package_json_path = project_path.join("package.json");
 
     if !package_json_path.exists() {
@@ -26,16 +43,312 @@ pub fn scan_project(path: String) -> Result<ProjectInfo, String> {
     }
 
     let content = fs::read_to_string(package_json_path).map_err(|e| e.to_string())?;
-    let pkg: PackageJson = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+    serde_json::from_str(&content).map_err(|e| e.to_string())
+}
+
+impl PackageJson {
+    fn has_dependency(&self, name: &str) -> bool {
+        self.dependencies.as_ref().is_some_and(|d| d.contains_key(name))
+            || self.dev_dependencies.as_ref().is_some_and(|d| d.contains_key(name))
+    }
+}
+
+// Checked in order, so meta-frameworks that ship with a bundler come before the bundler itself
+const FRAMEWORKS: &[(&str, &[&str])] = &[
+    ("Next.js", &["next"]),
+    ("Nuxt", &["nuxt", "nuxt3"]),
+    ("SvelteKit", &["@sveltejs/kit"]),
+    ("Astro", &["astro"]),
+    ("Remix", &["@remix-run/dev", "@remix-run/react"]),
+    ("Angular", &["@angular/core"]),
+    ("Vue CLI", &["@vue/cli-service"]),
+    ("Create React App", &["react-scripts"]),
+    ("Electron", &["electron"]),
+    ("Vite", &["vite"]),
+];
+
+const BUILD_TOOLS: &[(&str, &[&str], &[&str])] = &[
+    ("Vite", &["vite"], &["vite.config.js", "vite.config.ts", "vite.config.mjs", "vite.config.mts"]),
+    ("Rspack", &["@rspack/core", "@rspack/cli"], &["rspack.config.js", "rspack.config.ts"]),
+    ("webpack", &["webpack"], &["webpack.config.js", "webpack.config.ts"]),
+    ("Rollup", &["rollup"], &["rollup.config.js", "rollup.config.mjs", "rollup.config.ts"]),
+    ("Parcel", &["parcel"], &[".parcelrc"]),
+    ("esbuild", &["esbuild"], &[]),
+];
 
-    let mut scripts: Vec<String> = pkg.scripts.unwrap_or_default().keys().cloned().collect();
-    scripts.sort();
-    
-    let name = pkg.name.unwrap_or_else(|| project_path.file_name().unwrap().to_str().unwrap().to_string());
+fn detect_framework(pkg: &PackageJson) -> Option<String> {
+    FRAMEWORKS
+        .iter()
+        .find(|(_, deps)| deps.iter().any(|d| pkg.has_dependency(d)))
+        .map(|(name, _)| name.to_string())
+}
+
+fn detect_build_tool(project_path: &Path, pkg: &PackageJson, framework: Option<&str>) -> Option<String> {
+    // Frameworks that wrap their own bundler decide the build tool regardless of what else is installed
+    let implied = match framework {
+        Some("Vue CLI") | Some("Create React App") => Some("webpack"),
+        Some("Angular") => Some("Angular CLI"),
+        Some("Nuxt") | Some("SvelteKit") | Some("Astro") => Some("Vite"),
+        _ => None,
+    };
+    if let Some(tool) = implied {
+        return Some(tool.to_string());
+    }
+
+    BUILD_TOOLS
+        .iter()
+        .find(|(_, deps, configs)| {
+            deps.iter().any(|d| pkg.has_dependency(d))
+                || configs.iter().any(|c| project_path.join(c).exists())
+        })
+        .map(|(name, _, _)| name.to_string())
+}
+
+#[command]
+pub fn scan_project(path: String) -> Result<ProjectInfo, String> {
+    let project_path = Path::new(&path);
+    let mut pkg = read_package_json(project_path)?;
+
+    let script_commands: BTreeMap<String, String> = pkg.scripts.take().unwrap_or_default().into_iter().collect();
+    let scripts: Vec<String> = script_commands.keys().cloned().collect();
+
+    let framework = detect_framework(&pkg);
+    let build_tool = detect_build_tool(project_path, &pkg, framework.as_deref());
+
+    let name = pkg.name.take().unwrap_or_else(|| project_path.file_name().unwrap().to_str().unwrap().to_string());
 
     Ok(ProjectInfo {
         name,
         scripts,
         path,
+        script_commands,
+        version: pkg.version.take(),
+        description: pkg.description.take(),
+        dependency_count: pkg.dependencies.as_ref().map_or(0, |d| d.len()),
+        dev_dependency_count: pkg.dev_dependencies.as_ref().map_or(0, |d| d.len()),
+        framework,
+        build_tool,
     })
 }
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallStatus {
+    // "ok", "missing" or "stale"
+    state: String,
+    package_manager: Option<String>,
+    lockfile: Option<String>,
+    reason: Option<String>,
+    install_command: Option<String>,
+}
+
+// Lockfile, package manager and the markers each manager writes into node_modules after an install
+const LOCKFILES: &[(&str, &str, &[&str])] = &[
+    ("pnpm-lock.yaml", "pnpm", &[".modules.yaml"]),
+    ("yarn.lock", "yarn", &[".yarn-state.yml", ".yarn-integrity"]),
+    ("package-lock.json", "npm", &[".package-lock.json"]),
+    ("npm-shrinkwrap.json", "npm", &[".package-lock.json"]),
+    ("bun.lock", "bun", &[]),
+    ("bun.lockb", "bun", &[]),
+];
+
+fn modified_time(path: &Path) -> Option<std::time::SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[command]
+pub fn check_install_status(path: String) -> Result<InstallStatus, String> {
+    let project_path = Path::new(&path);
+    let pkg = read_package_json(project_path)?;
+    let declares_dependencies = pkg.dependencies.as_ref().is_some_and(|d| !d.is_empty())
+        || pkg.dev_dependencies.as_ref().is_some_and(|d| !d.is_empty());
+
+    let lock = LOCKFILES.iter().find(|(file, _, _)| project_path.join(file).exists());
+    let package_manager = lock.map(|(_, pm, _)| pm.to_string());
+    let install_command = Some(format!("{} install", package_manager.as_deref().unwrap_or("npm")));
+
+    let mut status = InstallStatus {
+        state: "ok".to_string(),
+        package_manager,
+        lockfile: lock.map(|(file, _, _)| file.to_string()),
+        reason: None,
+        install_command,
+    };
+
+    let node_modules = project_path.join("node_modules");
+    if !node_modules.is_dir() {
+        if declares_dependencies {
+            status.state = "missing".to_string();
+            status.reason = Some("node_modules not found".to_string());
+        }
+        return Ok(status);
+    }
+
+    // Fall back to the node_modules directory itself when the manager leaves no marker (e.g. bun)
+    let marker = lock
+        .and_then(|(_, _, markers)| markers.iter().map(|m| node_modules.join(m)).find(|p| p.exists()))
+        .unwrap_or_else(|| node_modules.clone());
+    let installed_at = match modified_time(&marker) {
+        Some(t) => t,
+        None => return Ok(status),
+    };
+
+    let mut newer = Vec::new();
+    if let Some((file, _, _)) = lock {
+        if modified_time(&project_path.join(file)).is_some_and(|t| t > installed_at) {
+            newer.push(file.to_string());
+        }
+    }
+    if modified_time(&project_path.join("package.json")).is_some_and(|t| t > installed_at) {
+        newer.push("package.json".to_string());
+    }
+
+    if !newer.is_empty() {
+        status.state = "stale".to_string();
+        status.reason = Some(format!("{} changed since the last install", newer.join(", ")));
+    }
+
+    Ok(status)
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeRecommendation {
+    // The project's own requirement, e.g. "lts/iron" or ">=18"
+    requirement: String,
+    // ".nvmrc", ".node-version" or "engines"
+    source: String,
+    // Best installed match and the directory holding its binary
+    installed: Option<String>,
+    installed_path: Option<String>,
+    // Newest release that satisfies the requirement, for offering an install
+    latest: Option<String>,
+}
+
+// Version files take precedence over engines.node, matching nvm and fnm. One we cannot
+// resolve ("system", "iojs") is skipped so it does not hide a usable engines.node
+fn node_requirement(project_path: &Path) -> Option<(String, String)> {
+    for file in [".nvmrc", ".node-version"] {
+        if let Ok(content) = fs::read_to_string(project_path.join(file)) {
+            let line = content.lines().map(|l| l.trim()).find(|l| !l.is_empty() && !l.starts_with('#'));
+            if let Some(line) = line.filter(|l| NodeSpec::parse(l).is_some()) {
+                return Some((line.to_string(), file.to_string()));
+            }
+        }
+    }
+    let pkg = read_package_json(project_path).ok()?;
+    let engine = pkg.engines?.remove("node").filter(|e| NodeSpec::parse(e).is_some())?;
+    Some((engine, "engines".to_string()))
+}
+
+#[command]
+pub async fn get_node_recommendation(path: String) -> Result<Option<NodeRecommendation>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let (requirement, source) = match node_requirement(Path::new(&path)) {
+            Some(found) => found,
+            None => return Ok(None),
+        };
+        let installed = discovery::resolve_installed(&requirement)?;
+        let latest = node_dist::resolve_remote(&node_dist::dist_url(), &requirement).ok();
+
+        Ok(Some(NodeRecommendation {
+            requirement,
+            source,
+            installed: installed.as_ref().map(|f| f.version.clone()),
+            installed_path: installed.map(|f| f.bin_dir.to_string_lossy().to_string()),
+            latest,
+        }))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn project(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("fp-project-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for (file, content) in files {
+            fs::write(dir.join(file), content).unwrap();
+        }
+        dir
+    }
+
+    fn package(json: &str) -> PackageJson {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn meta_frameworks_win_over_their_bundler() {
+        let pkg = package(r#"{"dependencies":{"next":"14","react":"18"},"devDependencies":{"webpack":"5"}}"#);
+        assert_eq!(detect_framework(&pkg).as_deref(), Some("Next.js"));
+        let pkg = package(r#"{"devDependencies":{"nuxt":"3","vite":"5"}}"#);
+        assert_eq!(detect_framework(&pkg).as_deref(), Some("Nuxt"));
+        let pkg = package(r#"{"devDependencies":{"vite":"5"}}"#);
+        assert_eq!(detect_framework(&pkg).as_deref(), Some("Vite"));
+        assert_eq!(detect_framework(&package(r#"{"dependencies":{"lodash":"4"}}"#)), None);
+        assert_eq!(detect_framework(&package("{}")), None);
+    }
+
+    #[test]
+    fn frameworks_imply_their_build_tool() {
+        let dir = project("implied", &[("vite.config.ts", "")]);
+        let pkg = package(r#"{"dependencies":{"react-scripts":"5"}}"#);
+        assert_eq!(detect_build_tool(&dir, &pkg, Some("Create React App")).as_deref(), Some("webpack"));
+        assert_eq!(detect_build_tool(&dir, &pkg, Some("Angular")).as_deref(), Some("Angular CLI"));
+        assert_eq!(detect_build_tool(&dir, &pkg, Some("SvelteKit")).as_deref(), Some("Vite"));
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn build_tool_from_dependency_or_config_file() {
+        let dir = project("tools", &[]);
+        let pkg = package(r#"{"devDependencies":{"rollup":"4","esbuild":"0.20"}}"#);
+        assert_eq!(detect_build_tool(&dir, &pkg, None).as_deref(), Some("Rollup"));
+        assert_eq!(detect_build_tool(&dir, &package("{}"), None), None);
+
+        fs::write(dir.join("webpack.config.js"), "").unwrap();
+        assert_eq!(detect_build_tool(&dir, &package("{}"), None).as_deref(), Some("webpack"));
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn scans_a_project() {
+        let dir = project(
+            "scan",
+            &[("package.json", r#"{"name":"demo","scripts":{"dev":"vite","build":"vite build"},"devDependencies":{"vite":"5"}}"#)],
+        );
+        let info = scan_project(dir.to_string_lossy().into_owned()).unwrap();
+        assert_eq!(info.name, "demo");
+        assert_eq!(info.scripts, vec!["build", "dev"]);
+        assert_eq!(info.framework.as_deref(), Some("Vite"));
+        assert_eq!(info.build_tool.as_deref(), Some("Vite"));
+        assert_eq!((info.dependency_count, info.dev_dependency_count), (0, 1));
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn version_file_wins_over_engines() {
+        let dir = project("nvmrc", &[(".nvmrc", "# pinned\nlts/iron\n"), ("package.json", r#"{"engines":{"node":">=18"}}"#)]);
+        assert_eq!(node_requirement(&dir), Some(("lts/iron".to_string(), ".nvmrc".to_string())));
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn unusable_version_file_falls_through_to_engines() {
+        let dir = project("system", &[(".nvmrc", "system\n"), ("package.json", r#"{"engines":{"node":"^20.1"}}"#)]);
+        assert_eq!(node_requirement(&dir), Some(("^20.1".to_string(), "engines".to_string())));
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn no_usable_requirement() {
+        let dir = project("iojs", &[(".nvmrc", "iojs"), ("package.json", r#"{"engines":{"node":"whatever"}}"#)]);
+        assert_eq!(node_requirement(&dir), None);
+        let _ = fs::remove_dir_all(dir);
+    }
+}
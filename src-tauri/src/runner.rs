@@ -12,8 +12,15 @@ use std::os::windows::process::CommandExt;
 #[cfg(target_os = "windows")]
 const CREATE_NO_WINDOW: u32 = 0x08000000;
 
+pub struct RunningProcess {
+    pub pid: u32,
+    pub path: String,
+    // Node directory the run was started with, empty for the system default
+    pub node_path: String,
+}
+
 pub struct ProcessState {
-    pub processes: Arc<Mutex<HashMap<String, u32>>>,
+    pub processes: Arc<Mutex<HashMap<String, RunningProcess>>>,
 }
 
 impl ProcessState {
@@ -30,21 +37,10 @@ pub fn cleanup_processes(state: &ProcessState) {
     // Assign lock result to a variable to ensure correct drop order and avoid "borrowed value does not live long enough" error
     let lock_result = processes.lock();
     if let Ok(mut lock) = lock_result {
-        for (id, pid) in lock.iter() {
+        for (id, process) in lock.iter() {
+            let pid = process.pid;
             println!("Killing process {} (PID: {})", id, pid);
-            #[cfg(target_os = "windows")]
-            {
-                let _ = Command::new("taskkill")
-                    .args(&["/PID", &pid.to_string(), "/F", "/T"])
-                    .creation_flags(CREATE_NO_WINDOW)
-                    .spawn();
-            }
-            #[cfg(not(target_os = "windows"))]
-            {
-                 let _ = Command::new("kill")
-                    .arg(pid.to_string())
-                    .spawn();
-            }
+            kill_process(pid);
         }
         lock.clear();
     }
@@ -125,6 +121,36 @@ pub fn run_project_command(
     script: String,
     package_manager: String,
     node_path: String,
+) -> Result<(), String> {
+    // Quote the script name to handle special characters (e.g. "build:prod")
+    let pm_args = format!("run \"{}\"", script);
+    spawn_package_manager(app, &state, id, path, &script, pm_args, package_manager, node_path)
+}
+
+#[tauri::command]
+pub fn install_dependencies(
+    app: AppHandle,
+    state: State<'_, ProcessState>,
+    id: String,
+    path: String,
+    package_manager: String,
+    node_path: String,
+) -> Result<(), String> {
+    spawn_package_manager(app, &state, id, path, "install", "install".to_string(), package_manager, node_path)
+}
+
+// Runs `<package manager> <pm_args>` in the project with the selected Node on PATH,
+// streaming output as "project-output" events and logging to logs/<project>/<log_name>.log
+#[allow(clippy::too_many_arguments)]
+fn spawn_package_manager(
+    app: AppHandle,
+    state: &ProcessState,
+    id: String,
+    path: String,
+    log_name: &str,
+    pm_args: String,
+    package_manager: String,
+    node_path: String,
 ) -> Result<(), String> {
     let processes = state.processes.clone();
     let mut processes_lock = processes.lock().map_err(|e| e.to_string())?;
@@ -176,7 +202,7 @@ pub fn run_project_command(
     }
 
     // Sanitize script name for filename
-    let safe_script = script.replace(|c: char| {
+    let safe_script = log_name.replace(|c: char| {
         matches!(c, '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*')
     }, "_");
     
@@ -249,8 +275,7 @@ pub fn run_project_command(
             "node".to_string()
         };
 
-        // Quote the script name to handle special characters (e.g. "build:prod")
-        full_cmd_str = format!("{} -v && {} run \"{}\"", node_executable, pm_cmd, script);
+        full_cmd_str = format!("{} -v && {} {}", node_executable, pm_cmd, pm_args);
         
         command_builder = Command::new("cmd");
         command_builder
@@ -332,13 +357,15 @@ pub fn run_project_command(
             "node".to_string()
         };
 
-        full_cmd_str = format!("{} -v && {} run \"{}\"", node_executable, pm_cmd, script);
+        full_cmd_str = format!("{} -v && {} {}", node_executable, pm_cmd, pm_args);
 
         command_builder = Command::new("sh");
         command_builder
             .arg("-c")
             .arg(&full_cmd_str)
             .env("PATH", new_path);
+        // Its own process group, so stopping the run reaches node and npm and not just sh
+        std::os::unix::process::CommandExt::process_group(&mut command_builder, 0);
     }
 
     // Common Env Vars
@@ -376,7 +403,7 @@ pub fn run_project_command(
     let mut child = command_builder.spawn().map_err(|e| e.to_string())?;
     let pid = child.id();
 
-    processes_lock.insert(id.clone(), pid);
+    processes_lock.insert(id.clone(), RunningProcess { pid, path: path.clone(), node_path: node_path.clone() });
     drop(processes_lock);
 
     let stdout = child.stdout.take().unwrap();
@@ -441,7 +468,7 @@ pub fn run_project_command(
     let processes_clone = state.processes.clone();
     let log_manager3 = log_manager.clone();
     thread::spawn(move || {
-        let _ = child.wait();
+        let success = child.wait().is_ok_and(|s| s.success());
         if let Ok(mut lock) = processes_clone.lock() {
             lock.remove(&id_clone3);
         }
@@ -453,36 +480,117 @@ pub fn run_project_command(
 
         let _ = app_clone3.emit(
             "project-exit",
-            serde_json::json!({ "id": id_clone3 }),
+            serde_json::json!({ "id": id_clone3, "success": success }),
         );
     });
 
     Ok(())
 }
 
+// Stops a run with everything it started: the process tree on Windows, the process group
+// led by the `sh` wrapper elsewhere
+fn kill_process(pid: u32) {
+    #[cfg(target_os = "windows")]
+    {
+        let _ = Command::new("taskkill")
+            .args(&["/PID", &pid.to_string(), "/F", "/T"])
+            .creation_flags(CREATE_NO_WINDOW)
+            .spawn();
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = Command::new("kill")
+            .args(["-TERM", "--", &format!("-{}", pid)])
+            .spawn();
+    }
+}
+
+// Whether anything in a run's process group is still alive after its `sh` exited
+#[cfg(not(target_os = "windows"))]
+fn group_alive(pid: u32) -> bool {
+    Command::new("kill")
+        .args(["-0", "--", &format!("-{}", pid)])
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|s| s.success())
+}
+
+// taskkill /T /F only returns once the whole tree is gone
+#[cfg(target_os = "windows")]
+fn group_alive(_pid: u32) -> bool {
+    false
+}
+
 #[tauri::command]
 pub fn stop_project_command(state: State<'_, ProcessState>, id: String) -> Result<(), String> {
     let processes = state.processes.clone();
     let lock = processes.lock().map_err(|e| e.to_string())?;
 
-    if let Some(pid) = lock.get(&id) {
-        #[cfg(target_os = "windows")]
-        {
-            let _ = Command::new("taskkill")
-                .args(&["/PID", &pid.to_string(), "/F", "/T"])
-                .creation_flags(CREATE_NO_WINDOW)
-                .spawn();
-        }
-        #[cfg(not(target_os = "windows"))]
-        {
-            let _ = Command::new("kill")
-                .arg(pid.to_string())
-                .spawn();
-        }
+    if let Some(RunningProcess { pid, .. }) = lock.get(&id) {
+        kill_process(*pid);
     }
     Ok(())
 }
 
+// Run ids and project paths of tracked processes started with a Node from one of `bin_dirs`
+pub fn processes_using_node(state: &ProcessState, bin_dirs: &[std::path::PathBuf]) -> Vec<(String, String)> {
+    let canonical: Vec<_> = bin_dirs.iter().filter_map(|d| fs::canonicalize(d).ok()).collect();
+    // A poisoned lock still holds every tracked run; skipping them would let a version in use
+    // be uninstalled
+    let lock = state.processes.lock().unwrap_or_else(|e| e.into_inner());
+    lock.iter()
+        .filter(|(_, p)| !p.node_path.is_empty())
+        .filter(|(_, p)| {
+            // The frontend may hand over node.exe itself rather than its directory
+            let node = std::path::Path::new(&p.node_path);
+            let dir = if node.is_file() { node.parent().unwrap_or(node) } else { node };
+            fs::canonicalize(dir).is_ok_and(|d| canonical.contains(&d))
+        })
+        .map(|(id, p)| (id.clone(), p.path.clone()))
+        .collect()
+}
+
+// Stops the given runs and waits until their exit threads have untracked them and nothing
+// they started is left; returns the ids still running after `timeout`
+pub fn stop_and_wait(state: &ProcessState, ids: &[String], timeout: std::time::Duration) -> Vec<String> {
+    let pids: Vec<(String, u32)> = match state.processes.lock() {
+        Ok(lock) => ids.iter().filter_map(|id| lock.get(id).map(|p| (id.clone(), p.pid))).collect(),
+        Err(_) => return ids.to_vec(),
+    };
+    for (_, pid) in &pids {
+        kill_process(*pid);
+    }
+
+    let deadline = std::time::Instant::now() + timeout;
+    loop {
+        let remaining: Vec<String> = match state.processes.lock() {
+            Ok(lock) => pids
+                .iter()
+                .filter(|(id, pid)| lock.contains_key(id) || group_alive(*pid))
+                .map(|(id, _)| id.clone())
+                .collect(),
+            Err(_) => return ids.to_vec(),
+        };
+        if remaining.is_empty() || std::time::Instant::now() >= deadline {
+            return remaining;
+        }
+        thread::sleep(std::time::Duration::from_millis(100));
+    }
+}
+
+// True if any tracked process was started in the given project directory; an unreadable
+// table counts as running so callers never delete files out from under a live process
+pub fn is_project_running(state: &ProcessState, path: &std::path::Path) -> bool {
+    let canonical = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    match state.processes.lock() {
+        Ok(lock) => lock.values().any(|p| {
+            let running = std::path::Path::new(&p.path);
+            running == path || fs::canonicalize(running).is_ok_and(|r| r == canonical)
+        }),
+        Err(_) => true,
+    }
+}
+
 #[tauri::command]
 pub fn open_in_editor(path: String, editor: String) -> Result<(), String> {
     let editor = editor.trim().trim_matches('"');
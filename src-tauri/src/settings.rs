@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config;
+use crate::http::HttpSettings;
+
+pub const SETTINGS_FILE: &str = "settings.json";
+
+// Settings the backend needs without a round trip to the frontend, stored in the config
+// directory beside the frontend's data.json but not writable from the webview
+#[derive(Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BackendSettings {
+    // Node.js distribution mirror, None meaning nodejs.org
+    pub node_mirror: Option<String>,
+    pub http: HttpSettings,
+}
+
+fn settings_path() -> Option<PathBuf> {
+    config::config_dir().ok().map(|d| d.join(SETTINGS_FILE))
+}
+
+pub fn load() -> BackendSettings {
+    settings_path()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn save(settings: &BackendSettings) -> Result<(), String> {
+    let path = settings_path().ok_or("The configuration directory is not set up yet")?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let content = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| e.to_string())
+}
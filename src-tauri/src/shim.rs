@@ -0,0 +1,130 @@
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[cfg(not(target_os = "windows"))]
+use crate::node_dist;
+
+// The default Node for new shells: `<managed root>/current` is a symlink to the selected
+// version's install directory, so `<managed root>/current/bin` is the one PATH entry users add
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DefaultNodeStatus {
+    bin_dir: String,
+    // Install directory `current` points at, None when no default was chosen yet
+    target: Option<String>,
+    on_path: bool,
+    // Shell profile that already adds bin_dir to PATH
+    configured_in: Option<String>,
+    // What to add to which file, when the directory is not on PATH yet
+    instructions: Option<String>,
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn current_dir() -> Option<PathBuf> {
+    node_dist::managed_root().map(|r| r.join("current"))
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn bin_dir() -> Option<PathBuf> {
+    current_dir().map(|d| d.join("bin"))
+}
+
+// Points `current` at the version whose binary lives in `node_bin_dir`, replacing the old
+// link atomically so running shells never see it missing
+#[cfg(not(target_os = "windows"))]
+pub fn set_default(node_bin_dir: &Path) -> Result<PathBuf, String> {
+    let current = current_dir().ok_or("Could not determine the data directory")?;
+    let target = match node_bin_dir.file_name() {
+        Some(name) if name == "bin" => node_bin_dir.parent().unwrap_or(node_bin_dir),
+        _ => return Err(format!("{} is not a bin directory", node_bin_dir.display())),
+    };
+    if let Some(parent) = current.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    let staging = current.with_file_name("current.tmp");
+    let _ = fs::remove_file(&staging);
+    std::os::unix::fs::symlink(target, &staging).map_err(|e| e.to_string())?;
+    fs::rename(&staging, &current).map_err(|e| {
+        let _ = fs::remove_file(&staging);
+        e.to_string()
+    })?;
+    Ok(current.join("bin"))
+}
+
+// Install directory the default Node currently points at
+pub fn default_target() -> Option<PathBuf> {
+    #[cfg(target_os = "windows")]
+    let link = std::env::var_os("NVM_SYMLINK").map(PathBuf::from);
+    #[cfg(not(target_os = "windows"))]
+    let link = current_dir();
+    link.and_then(|l| fs::read_link(l).ok())
+}
+
+fn is_on_path(dir: &Path) -> bool {
+    std::env::var_os("PATH").is_some_and(|p| std::env::split_paths(&p).any(|p| p == dir))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn profile_files(home: &Path) -> Vec<PathBuf> {
+    [".bashrc", ".bash_profile", ".zshrc", ".zprofile", ".profile", ".config/fish/config.fish"]
+        .iter()
+        .map(|f| home.join(f))
+        .collect()
+}
+
+#[cfg(not(target_os = "windows"))]
+fn instructions(bin_dir: &str) -> String {
+    let shell = std::env::var("SHELL").unwrap_or_default();
+    let shell = shell.rsplit('/').next().unwrap_or_default();
+    match shell {
+        "fish" => format!("Run: fish_add_path \"{}\"", bin_dir),
+        "zsh" => format!("Add to ~/.zshrc: export PATH=\"{}:$PATH\"", bin_dir),
+        _ => format!("Add to ~/.bashrc (or ~/.profile): export PATH=\"{}:$PATH\"", bin_dir),
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+#[tauri::command]
+pub fn get_default_node_status() -> Result<DefaultNodeStatus, String> {
+    let bin = bin_dir().ok_or("Could not determine the data directory")?;
+    let bin_str = bin.to_string_lossy().to_string();
+
+    // A desktop launch rarely inherits the login shell's PATH, so also look at the profiles
+    let on_path = is_on_path(&bin);
+    let configured_in = dirs::home_dir().and_then(|home| {
+        profile_files(&home).into_iter().find(|file| {
+            fs::read_to_string(file).is_ok_and(|content| {
+                content
+                    .lines()
+                    .any(|l| !l.trim_start().starts_with('#') && l.contains(&bin_str))
+            })
+        })
+    });
+
+    let target = default_target();
+    let instructions = if on_path || configured_in.is_some() { None } else { Some(instructions(&bin_str)) };
+    Ok(DefaultNodeStatus {
+        bin_dir: bin_str,
+        target: target.map(|t| t.to_string_lossy().to_string()),
+        on_path,
+        configured_in: configured_in.map(|p| p.to_string_lossy().to_string()),
+        instructions,
+    })
+}
+
+#[cfg(target_os = "windows")]
+#[tauri::command]
+pub fn get_default_node_status() -> Result<DefaultNodeStatus, String> {
+    // nvm-windows owns the switch through the symlink directory it adds to PATH at install time
+    let bin = std::env::var("NVM_SYMLINK").map_err(|_| "nvm-windows is not installed".to_string())?;
+    let on_path = is_on_path(Path::new(&bin));
+    Ok(DefaultNodeStatus {
+        target: fs::read_link(&bin).ok().map(|t| t.to_string_lossy().to_string()),
+        instructions: (!on_path).then(|| format!("Add {} to the PATH environment variable", bin)),
+        bin_dir: bin,
+        on_path,
+        configured_in: None,
+    })
+}
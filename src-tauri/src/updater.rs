@@ -1,10 +1,359 @@
-use std::fs::File;
+use std::fs::{self, File, OpenOptions};
 use std::process::Command;
 use std::env;
-use tauri::{AppHandle, Emitter, State};
+use tauri::{AppHandle, Emitter, Manager, State};
 use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use minisign_verify::{PublicKey, Signature};
+use reqwest::header::{HeaderValue, CONTENT_RANGE, ETAG, IF_RANGE, LAST_MODIFIED, RANGE};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::http::{self, HttpClient};
+use crate::version::Version;
+
+// minisign public key (the base64 line of the .pub file) that update signatures must verify
+// against. Releases are signed with the matching secret key, which CI reads from the
+// TAURI_SIGNING_PRIVATE_KEY secret
+const UPDATE_PUBLIC_KEY: &str = "RWS4/+30M2jUnZ9E+Ri7CWHaGfzt0hXEpze+1l/TX7WQ6uuztQLWMYwd";
+
+const RELEASES_URL: &str = "https://api.github.com/repos/cuteyuchen/fp-node-manager/releases";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AssetKind {
+    Nsis,
+    Msi,
+    AppImage,
+    Deb,
+    Rpm,
+    Dmg,
+}
+
+impl AssetKind {
+    pub fn from_name(name: &str) -> Option<Self> {
+        let lower = name.to_ascii_lowercase();
+        if lower.ends_with(".appimage") {
+            Some(Self::AppImage)
+        } else if lower.ends_with(".deb") {
+            Some(Self::Deb)
+        } else if lower.ends_with(".rpm") {
+            Some(Self::Rpm)
+        } else if lower.ends_with(".dmg") {
+            Some(Self::Dmg)
+        } else if lower.ends_with(".msi") {
+            Some(Self::Msi)
+        } else if lower.ends_with(".exe") {
+            Some(Self::Nsis)
+        } else {
+            None
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Nsis => "exe",
+            Self::Msi => "msi",
+            Self::AppImage => "AppImage",
+            Self::Deb => "deb",
+            Self::Rpm => "rpm",
+            Self::Dmg => "dmg",
+        }
+    }
+}
+
+// Spellings of each architecture in bundle names: Tauri uses x64 for NSIS and dmg,
+// amd64 for deb and AppImage, x86_64 for rpm
+const ARCH_NAMES: &[(&str, &[&str])] = &[
+    ("x86_64", &["x64", "amd64"]),
+    ("aarch64", &["aarch64", "arm64"]),
+    ("x86", &["x86", "i386", "i686"]),
+];
+
+fn matches_host_arch(name: &str) -> bool {
+    // "x86_64" would otherwise split into a token that reads as 32-bit x86
+    let lower = name.to_ascii_lowercase().replace("x86_64", "x64").replace("x86-64", "x64");
+    let tokens: Vec<&str> = lower.split(|c: char| !c.is_ascii_alphanumeric()).collect();
+    let mentions = |aliases: &[&str]| aliases.iter().any(|a| tokens.contains(a));
+    match ARCH_NAMES.iter().find(|(arch, _)| *arch == env::consts::ARCH) {
+        // Names without any architecture are universal builds
+        Some((_, aliases)) => mentions(aliases) || !ARCH_NAMES.iter().any(|(_, other)| mentions(other)),
+        None => false,
+    }
+}
+
+// Asset kinds this system can install, most preferred first
+fn preferred_kinds() -> Vec<AssetKind> {
+    match env::consts::OS {
+        "windows" => vec![AssetKind::Nsis, AssetKind::Msi],
+        "macos" => vec![AssetKind::Dmg],
+        "linux" => {
+            // Stay with the format the app was installed from
+            let has = |tool: &str| Command::new("which").arg(tool).output().is_ok_and(|o| o.status.success());
+            if env::var_os("APPIMAGE").is_some() {
+                vec![AssetKind::AppImage]
+            } else if has("dpkg") {
+                vec![AssetKind::Deb, AssetKind::AppImage]
+            } else if has("rpm") {
+                vec![AssetKind::Rpm, AssetKind::AppImage]
+            } else {
+                vec![AssetKind::AppImage]
+            }
+        }
+        _ => Vec::new(),
+    }
+}
+
+// Picks the release asset for this OS, architecture and install format from asset file names
+pub fn select_asset<'a>(names: &[&'a str]) -> Option<&'a str> {
+    let candidates: Vec<&str> = names.iter().copied().filter(|n| matches_host_arch(n)).collect();
+    preferred_kinds()
+        .into_iter()
+        .find_map(|kind| candidates.iter().copied().find(|n| AssetKind::from_name(n) == Some(kind)))
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateAsset {
+    name: String,
+    url: String,
+    size: Option<u64>,
+    sha256: Option<String>,
+    signature: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateInfo {
+    current_version: String,
+    available: bool,
+    version: Option<String>,
+    channel: String,
+    notes: Option<String>,
+    published_at: Option<String>,
+    // Set when the running version is older than the minimum the releases still support
+    required: bool,
+    minimum_version: Option<String>,
+    asset: Option<UpdateAsset>,
+}
+
+// Our own release manifest:
+// { "minimumVersion": "0.1.5", "releases": [{ "version", "channel", "notes", "pubDate", "assets": [...] }] }
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Manifest {
+    minimum_version: Option<String>,
+    releases: Vec<ManifestRelease>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ManifestRelease {
+    version: String,
+    #[serde(default)]
+    channel: Option<String>,
+    notes: Option<String>,
+    pub_date: Option<String>,
+    #[serde(default)]
+    assets: Vec<ManifestAsset>,
+}
+
+#[derive(Deserialize)]
+struct ManifestAsset {
+    name: String,
+    url: String,
+    size: Option<u64>,
+    sha256: Option<String>,
+    signature: Option<String>,
+}
+
+// The parts of GitHub's releases API we use
+#[derive(Deserialize)]
+struct GithubRelease {
+    tag_name: String,
+    body: Option<String>,
+    #[serde(default)]
+    prerelease: bool,
+    #[serde(default)]
+    draft: bool,
+    published_at: Option<String>,
+    #[serde(default)]
+    assets: Vec<GithubAsset>,
+}
+
+#[derive(Deserialize)]
+struct GithubAsset {
+    name: String,
+    browser_download_url: String,
+    size: Option<u64>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ReleaseFeed {
+    Manifest(Manifest),
+    GithubList(Vec<GithubRelease>),
+    Github(GithubRelease),
+}
+
+struct Release {
+    version: Version,
+    notes: Option<String>,
+    published_at: Option<String>,
+    assets: Vec<UpdateAsset>,
+}
+
+// GitHub releases declare their minimum with a "minimum-version: 0.1.5" line in the notes,
+// usually inside an HTML comment
+fn declared_minimum(body: &str) -> Option<Version> {
+    body.lines()
+        .find_map(|l| l.split_once("minimum-version:"))
+        .and_then(|(_, rest)| Version::parse(rest.trim().trim_end_matches("-->").trim()))
+}
+
+// Releases offered on `channel`, plus the minimum supported version; "beta" also sees stable
+// releases so beta users move on to a stable release once it is newer
+fn releases_for(feed: ReleaseFeed, channel: &str) -> (Vec<Release>, Option<Version>) {
+    let include = |version: &Version, release_channel: &str| {
+        channel == "beta" || (release_channel == "stable" && !version.is_prerelease())
+    };
+
+    match feed {
+        ReleaseFeed::Manifest(manifest) => {
+            let releases = manifest
+                .releases
+                .into_iter()
+                .filter_map(|r| {
+                    let version = Version::parse(&r.version)?;
+                    include(&version, r.channel.as_deref().unwrap_or("stable")).then(|| Release {
+                        version,
+                        notes: r.notes,
+                        published_at: r.pub_date,
+                        assets: r
+                            .assets
+                            .into_iter()
+                            .map(|a| UpdateAsset { name: a.name, url: a.url, size: a.size, sha256: a.sha256, signature: a.signature })
+                            .collect(),
+                    })
+                })
+                .collect();
+            (releases, manifest.minimum_version.as_deref().and_then(Version::parse))
+        }
+        ReleaseFeed::GithubList(list) => github_releases(list, include),
+        ReleaseFeed::Github(release) => github_releases(vec![release], include),
+    }
+}
+
+fn github_releases(list: Vec<GithubRelease>, include: impl Fn(&Version, &str) -> bool) -> (Vec<Release>, Option<Version>) {
+    let minimum = list.iter().filter_map(|r| declared_minimum(r.body.as_deref()?)).max();
+    let releases = list
+        .into_iter()
+        .filter(|r| !r.draft)
+        .filter_map(|r| {
+            let version = Version::parse(&r.tag_name)?;
+            include(&version, if r.prerelease { "beta" } else { "stable" }).then(|| Release {
+                version,
+                notes: r.body,
+                published_at: r.published_at,
+                assets: r
+                    .assets
+                    .into_iter()
+                    .map(|a| UpdateAsset { name: a.name, url: a.browser_download_url, size: a.size, sha256: None, signature: None })
+                    .collect(),
+            })
+        })
+        .collect();
+    (releases, minimum)
+}
+
+fn fetch_text(http: &HttpClient, url: &str) -> Result<String, String> {
+    http.get(url)
+        .and_then(|r| r.error_for_status().and_then(|r| r.text()).map_err(|e| e.to_string()))
+        .map_err(|e| format!("Failed to fetch {}: {}", url, e))
+}
+
+// GitHub releases carry the digest and signature as sibling assets: "<file>.sig" as written by
+// Tauri's signer, and "<file>.sha256" or a SHA256SUMS listing
+fn fill_github_checksums(http: &HttpClient, asset: &mut UpdateAsset, siblings: &[UpdateAsset]) {
+    let sibling = |suffix: &str| siblings.iter().find(|a| a.name == format!("{}{}", asset.name, suffix));
+
+    if asset.signature.is_none() {
+        asset.signature = sibling(".sig").and_then(|a| fetch_text(http, &a.url).ok());
+    }
+    if asset.sha256.is_none() {
+        let listing = sibling(".sha256").or_else(|| siblings.iter().find(|a| a.name.starts_with("SHA256SUMS")));
+        asset.sha256 = listing.and_then(|a| fetch_text(http, &a.url).ok()).and_then(|content| {
+            content.lines().find_map(|line| {
+                let mut parts = line.split_whitespace();
+                let digest = parts.next()?;
+                match parts.next().map(|f| f.trim_start_matches('*')) {
+                    Some(file) if file != asset.name => None,
+                    _ => Some(digest.to_ascii_lowercase()),
+                }
+            })
+        });
+    }
+}
+
+fn check(current: &str, channel: &str, feed_url: &str) -> Result<UpdateInfo, String> {
+    let current_version = Version::parse(current).ok_or_else(|| format!("Invalid app version: {}", current))?;
+    let http = HttpClient::new()?;
+    let content = fetch_text(&http, feed_url)?;
+    let feed: ReleaseFeed = serde_json::from_str(&content).map_err(|e| format!("Unrecognised release feed: {}", e))?;
+    let (releases, minimum) = releases_for(feed, channel);
+
+    let mut info = UpdateInfo {
+        current_version: current.to_string(),
+        available: false,
+        version: None,
+        channel: channel.to_string(),
+        notes: None,
+        published_at: None,
+        required: minimum.as_ref().is_some_and(|m| &current_version < m),
+        minimum_version: minimum.map(|m| m.to_string()),
+        asset: None,
+    };
+
+    let latest = releases
+        .into_iter()
+        .filter(|r| r.version > current_version)
+        .max_by(|a, b| a.version.cmp(&b.version));
+    if let Some(release) = latest {
+        let names: Vec<&str> = release.assets.iter().map(|a| a.name.as_str()).collect();
+        let mut asset = select_asset(&names).and_then(|name| release.assets.iter().find(|a| a.name == name)).cloned();
+        if let Some(asset) = asset.as_mut() {
+            fill_github_checksums(&http, asset, &release.assets);
+        }
+
+        info.available = true;
+        info.version = Some(release.version.to_string());
+        info.notes = release.notes;
+        info.published_at = release.published_at;
+        info.asset = asset;
+    }
+    Ok(info)
+}
+
+// Looks for a newer release on `channel` ("stable" or "beta") in `manifest_url`, which may be
+// our own manifest or a GitHub releases API response, defaulting to this repository's releases
+#[tauri::command]
+pub async fn check_for_update(app: AppHandle, channel: Option<String>, manifest_url: Option<String>) -> Result<UpdateInfo, String> {
+    let channel = channel.unwrap_or_else(|| "stable".to_string());
+    if channel != "stable" && channel != "beta" {
+        return Err(format!("Unknown release channel: {}", channel));
+    }
+    let current = app.package_info().version.to_string();
+    let feed_url = manifest_url.unwrap_or_else(|| RELEASES_URL.to_string());
+
+    tauri::async_runtime::spawn_blocking(move || check(&current, &channel, &feed_url))
+        .await
+        .map_err(|e| e.to_string())?
+}
 
 pub struct UpdateState {
     pub is_cancelling: Arc<AtomicBool>,
@@ -18,80 +367,1084 @@ impl UpdateState {
     }
 }
 
+// Lets the update dialog pick from a release's asset names without knowing bundle conventions
+#[tauri::command]
+pub fn get_update_asset(names: Vec<String>) -> Option<String> {
+    let names: Vec<&str> = names.iter().map(|n| n.as_str()).collect();
+    select_asset(&names).map(|n| n.to_string())
+}
+
 #[tauri::command]
 pub fn cancel_update(state: State<UpdateState>) {
     state.is_cancelling.store(true, Ordering::SeqCst);
 }
 
+// What a partial download was fetched from, kept next to the .part file so a later
+// attempt only resumes when the server still has the same file
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PartialDownload {
+    url: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+impl PartialDownload {
+    // If-Range only accepts a strong ETag or a date
+    fn validator(&self) -> Option<&str> {
+        self.etag
+            .as_deref()
+            .filter(|e| !e.starts_with("W/"))
+            .or(self.last_modified.as_deref())
+    }
+}
+
+fn partial_paths(dest: &Path) -> (PathBuf, PathBuf) {
+    let name = dest.file_name().unwrap_or_default().to_string_lossy();
+    (dest.with_file_name(format!("{}.part", name)), dest.with_file_name(format!("{}.part.json", name)))
+}
+
+// First byte offset of a "bytes 500-999/1000" Content-Range
+fn range_start(response: &reqwest::blocking::Response) -> Option<u64> {
+    let value = response.headers().get(CONTENT_RANGE)?.to_str().ok()?;
+    value.strip_prefix("bytes ")?.split('-').next()?.trim().parse().ok()
+}
+
+// Payload of the "download-progress" event
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadProgress {
+    downloaded: u64,
+    // None when the server sends no Content-Length
+    total: Option<u64>,
+    bytes_per_second: u64,
+    eta_seconds: Option<u64>,
+}
+
+// At most this often, so a fast connection does not flood the frontend
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+// Turns raw byte counts into throttled progress with a smoothed throughput
+struct ProgressTracker {
+    last: Option<(Instant, u64)>,
+    bytes_per_second: f64,
+}
+
+impl ProgressTracker {
+    fn new() -> Self {
+        Self { last: None, bytes_per_second: 0.0 }
+    }
+
+    // Returns the progress to report, or None while the last report is still recent
+    fn update(&mut self, now: Instant, downloaded: u64, total: Option<u64>) -> Option<DownloadProgress> {
+        let finished = total.is_some_and(|t| downloaded >= t);
+        match self.last {
+            // A restarted download counts from zero again, so start measuring afresh
+            Some((at, bytes)) if downloaded >= bytes => {
+                let elapsed = now.duration_since(at);
+                if elapsed < PROGRESS_INTERVAL && !finished {
+                    return None;
+                }
+                let current = (downloaded - bytes) as f64 / elapsed.as_secs_f64().max(0.001);
+                // Moving average so one slow chunk does not make the estimate jump around
+                self.bytes_per_second = if self.bytes_per_second == 0.0 {
+                    current
+                } else {
+                    0.3 * current + 0.7 * self.bytes_per_second
+                };
+            }
+            _ => self.bytes_per_second = 0.0,
+        }
+        self.last = Some((now, downloaded));
+
+        let eta_seconds = total
+            .filter(|_| self.bytes_per_second > 0.0)
+            .map(|t| (t.saturating_sub(downloaded) as f64 / self.bytes_per_second).ceil() as u64);
+        Some(DownloadProgress {
+            downloaded,
+            total,
+            bytes_per_second: self.bytes_per_second as u64,
+            eta_seconds,
+        })
+    }
+}
+
+// Streams `url` to `dest` and returns the hex SHA-256. Bytes land in `<dest>.part` first;
+// if a previous attempt for the same URL was interrupted, only the rest is requested with
+// Range/If-Range, and a server that ignores the range or has a different file sends it whole
+fn download(http: &HttpClient, url: &str, dest: &Path, is_cancelling: &AtomicBool, on_progress: &mut dyn FnMut(u64, u64)) -> Result<String, String> {
+    let (part, meta_path) = partial_paths(dest);
+    let previous = fs::read_to_string(&meta_path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<PartialDownload>(&content).ok())
+        .filter(|meta| meta.url == url);
+    let mut existing = previous.as_ref().and_then(|_| fs::metadata(&part).ok()).map_or(0, |m| m.len());
+
+    let mut request = http.client.get(url);
+    if let Some(validator) = previous.as_ref().and_then(|m| m.validator()).filter(|_| existing > 0) {
+        request = request
+            .header(RANGE, format!("bytes={}-", existing))
+            .header(IF_RANGE, validator);
+    }
+    let mut response = http.send(request)?;
+
+    // 416 means the part already holds the whole file or more, e.g. when the last attempt
+    // stopped right before the rename; it cannot be resumed, so start over
+    if response.status() == StatusCode::RANGE_NOT_SATISFIABLE && existing > 0 {
+        let _ = fs::remove_file(&part);
+        let _ = fs::remove_file(&meta_path);
+        existing = 0;
+        response = http.get(url)?;
+    }
+
+    let resumed = response.status() == StatusCode::PARTIAL_CONTENT && range_start(&response) == Some(existing);
+    if !resumed {
+        response = response.error_for_status().map_err(|e| e.to_string())?;
+        if response.status() == StatusCode::PARTIAL_CONTENT {
+            return Err("Server sent an unexpected byte range".to_string());
+        }
+    }
+
+    let header = |name| response.headers().get(name).and_then(|v: &HeaderValue| v.to_str().ok()).map(|v| v.to_string());
+    let meta = PartialDownload { url: url.to_string(), etag: header(ETAG), last_modified: header(LAST_MODIFIED) };
+    fs::write(&meta_path, serde_json::to_string(&meta).map_err(|e| e.to_string())?).map_err(|e| e.to_string())?;
+
+    let mut hasher = Sha256::new();
+    let mut downloaded: u64 = 0;
+    let mut file = if resumed {
+        // The digest covers the whole file, so feed it what is already on disk
+        let mut previous_bytes = File::open(&part).map_err(|e| e.to_string())?;
+        downloaded = std::io::copy(&mut previous_bytes, &mut hasher).map_err(|e| e.to_string())?;
+        OpenOptions::new().append(true).open(&part).map_err(|e| e.to_string())?
+    } else {
+        File::create(&part).map_err(|e| e.to_string())?
+    };
+    let total_size = response.content_length().map_or(0, |len| len + downloaded);
+
+    let mut buffer = [0; 16384]; // 16KB buffer
+
+    loop {
+        if is_cancelling.load(Ordering::SeqCst) {
+            let _ = fs::remove_file(&part);
+            let _ = fs::remove_file(&meta_path);
+            return Err("Update cancelled by user".to_string());
+        }
+
+        // On a dropped connection the partial file stays for the next attempt to resume
+        let bytes_read = response
+            .read(&mut buffer)
+            .map_err(|e| format!("Download interrupted at {} bytes: {}", downloaded, e))?;
+        if bytes_read == 0 {
+            break;
+        }
+        file.write_all(&buffer[..bytes_read]).map_err(|e| e.to_string())?;
+        hasher.update(&buffer[..bytes_read]);
+        downloaded += bytes_read as u64;
+        on_progress(downloaded, total_size);
+    }
+
+    file.flush().map_err(|e| e.to_string())?;
+    drop(file);
+    if total_size > 0 && downloaded != total_size {
+        return Err(format!("Download interrupted at {} of {} bytes", downloaded, total_size));
+    }
+
+    fs::rename(&part, dest).map_err(|e| e.to_string())?;
+    let _ = fs::remove_file(&meta_path);
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+// Checks the downloaded file against the published SHA-256 and its detached minisign signature.
+// Both are checked on the bytes read through `file`, the handle the installer is then given
+fn verify(mut file: &File, expected_sha256: &str, signature: &str, public_key: &str) -> Result<(), String> {
+    let mut content = Vec::new();
+    file.read_to_end(&mut content).map_err(|e| e.to_string())?;
+    let actual_sha256 = format!("{:x}", Sha256::digest(&content));
+    if !actual_sha256.eq_ignore_ascii_case(expected_sha256.trim()) {
+        return Err(format!("Checksum mismatch: expected {}, got {}", expected_sha256.trim(), actual_sha256));
+    }
+
+    let public_key = PublicKey::from_base64(public_key.trim()).map_err(|e| format!("Invalid update public key: {}", e))?;
+    // Tauri's signer writes the minisign signature file base64-encoded
+    let signature = match signature.trim() {
+        s if s.starts_with("untrusted comment:") => s.to_string(),
+        s => BASE64
+            .decode(s)
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+            .ok_or("Invalid update signature encoding")?,
+    };
+    let signature = Signature::decode(&signature).map_err(|e| format!("Invalid update signature: {}", e))?;
+    public_key
+        .verify(&content, &signature, false)
+        .map_err(|_| "Update signature verification failed".to_string())
+}
+
+// Hands the verified file to whatever installs that format on this OS. A silent install
+// asks no questions (beyond a password prompt for packages) and restarts the app afterwards
+fn launch_installer(kind: AssetKind, file: &Path, silent: bool, data_dir: &Path) -> Result<(), String> {
+    match kind {
+        // The installer cannot replace files while the app runs, so a helper waits for it to exit
+        AssetKind::Nsis | AssetKind::Msi if silent => run_after_exit(&silent_install_script(kind, file, &exit_code_path(data_dir))),
+        // Using cmd /c start to ensure it runs independently
+        AssetKind::Nsis | AssetKind::Msi => {
+            let mut cmd = Command::new("cmd");
+            cmd.args(["/C", "start", ""]).arg(file);
+            cmd.spawn().map(|_| ()).map_err(|e| e.to_string())
+        }
+        AssetKind::AppImage => {
+            let target = install_target(kind)?;
+            replace_appimage(file, &target)?;
+            relaunch(kind, &target)
+        }
+        AssetKind::Deb | AssetKind::Rpm => {
+            if install_package(kind, file, silent)? {
+                relaunch(kind, &running_exe()?)
+            } else {
+                Ok(())
+            }
+        }
+        AssetKind::Dmg => {
+            let bundle = install_target(kind)?;
+            install_dmg(file, &bundle)?;
+            relaunch(kind, &bundle)
+        }
+    }
+}
+
+// Arguments the app was started with, so a restart after an update opens the same way
+fn startup_args() -> Vec<String> {
+    env::args().skip(1).collect()
+}
+
+fn relaunch(kind: AssetKind, target: &Path) -> Result<(), String> {
+    let mut cmd = if kind == AssetKind::Dmg {
+        let mut open = Command::new("open");
+        open.arg("-n").arg(target).arg("--args");
+        open
+    } else {
+        Command::new(target)
+    };
+    cmd.args(startup_args()).spawn().map(|_| ()).map_err(|e| e.to_string())
+}
+
+// On Linux the path of a replaced binary reads "<path> (deleted)"; the new one is at <path>
+fn running_exe() -> Result<PathBuf, String> {
+    let exe = env::current_exe().map_err(|e| e.to_string())?;
+    let path = exe.to_string_lossy();
+    Ok(path.strip_suffix(" (deleted)").map(PathBuf::from).unwrap_or_else(|| exe.clone()))
+}
+
+fn ps_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+// Runs the installer with NSIS's or msiexec's silent flags and records its exit code for
+// take_update_report; Start-Process joins -ArgumentList with spaces, hence the inner quotes
+fn silent_install_script(kind: AssetKind, file: &Path, exit_code: &Path) -> String {
+    let file = file.to_string_lossy();
+    let start = match kind {
+        AssetKind::Msi => format!(
+            "Start-Process msiexec -ArgumentList '/i',{},'/quiet','/norestart'",
+            ps_quote(&format!("\"{}\"", file))
+        ),
+        _ => format!("Start-Process {} -ArgumentList '/S'", ps_quote(&file)),
+    };
+    format!(
+        "$p = {} -Wait -PassThru; Set-Content -Path {} -Value $p.ExitCode",
+        start,
+        ps_quote(&exit_code.to_string_lossy())
+    )
+}
+
+// Spawns a hidden PowerShell that waits for this process to exit, runs `steps`, then starts
+// the app again with its original arguments
+#[cfg(target_os = "windows")]
+fn run_after_exit(steps: &str) -> Result<(), String> {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+    let exe = env::current_exe().map_err(|e| e.to_string())?;
+    let args: Vec<String> = startup_args().iter().map(|a| ps_quote(&format!("\"{}\"", a))).collect();
+    let args = if args.is_empty() { String::new() } else { format!(" -ArgumentList {}", args.join(",")) };
+    let script = format!(
+        "Wait-Process -Id {} -ErrorAction SilentlyContinue; {}; Start-Process {}{}",
+        std::process::id(),
+        steps,
+        ps_quote(&exe.to_string_lossy()),
+        args
+    );
+    Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+        .creation_flags(CREATE_NO_WINDOW)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn run_after_exit(_steps: &str) -> Result<(), String> {
+    Err("Installer updates are only supported on Windows".to_string())
+}
+
+// Swaps the running AppImage for the new one; the old inode stays valid for the running
+// process, and the rename is atomic because the new file is staged in the same directory
+#[cfg(unix)]
+fn replace_appimage(file: &Path, current: &Path) -> Result<(), String> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let staged = current.with_extension("AppImage.new");
+
+    fs::copy(file, &staged).map_err(|e| format!("Cannot write next to {}: {}", current.display(), e))?;
+    let result = fs::set_permissions(&staged, fs::Permissions::from_mode(0o755))
+        .and_then(|_| fs::rename(&staged, current))
+        .map_err(|e| e.to_string());
+    if result.is_err() {
+        let _ = fs::remove_file(&staged);
+    }
+    result
+}
+
+#[cfg(not(unix))]
+fn replace_appimage(_file: &Path, _current: &Path) -> Result<(), String> {
+    Err("AppImage updates are only supported on Linux".to_string())
+}
+
+// Installs a .deb or .rpm through the distribution's package manager, asking for
+// privileges with pkexec, and falls back to the desktop's software installer unless the
+// install must be silent. Returns whether the package is installed by the time it returns
+fn install_package(kind: AssetKind, file: &Path, silent: bool) -> Result<bool, String> {
+    let has = |tool: &str| Command::new("which").arg(tool).output().is_ok_and(|o| o.status.success());
+    let manager: Option<&[&str]> = match kind {
+        AssetKind::Deb if has("apt-get") => Some(&["apt-get", "install", "-y"]),
+        AssetKind::Deb if has("dpkg") => Some(&["dpkg", "-i"]),
+        AssetKind::Rpm if has("dnf") => Some(&["dnf", "install", "-y"]),
+        AssetKind::Rpm if has("zypper") => Some(&["zypper", "--non-interactive", "install"]),
+        AssetKind::Rpm if has("rpm") => Some(&["rpm", "-U"]),
+        _ => None,
+    };
+
+    match manager {
+        Some(args) if has("pkexec") => {
+            let status = Command::new("pkexec")
+                .args(args)
+                .arg(file)
+                .status()
+                .map_err(|e| e.to_string())?;
+            if !status.success() {
+                return Err(format!("{} failed with {}", args[0], status));
+            }
+            Ok(true)
+        }
+        _ if silent => Err("No package manager is available for a silent install".to_string()),
+        _ => Command::new("xdg-open").arg(file).spawn().map(|_| false).map_err(|e| e.to_string()),
+    }
+}
+
+// Copies the .app bundle out of the disk image over the running one
+fn install_dmg(file: &Path, bundle: &Path) -> Result<(), String> {
+    let mount = env::temp_dir().join(format!("frontend-manager-update-{}", std::process::id()));
+    let attached = Command::new("hdiutil")
+        .args(["attach", "-nobrowse", "-quiet", "-mountpoint"])
+        .arg(&mount)
+        .arg(file)
+        .status()
+        .map_err(|e| e.to_string())?;
+    if !attached.success() {
+        return Err("Failed to mount the update disk image".to_string());
+    }
+
+    let result = fs::read_dir(&mount)
+        .map_err(|e| e.to_string())
+        .and_then(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .find(|p| p.extension().is_some_and(|e| e == "app"))
+                .ok_or_else(|| "No application found in the update disk image".to_string())
+        })
+        .and_then(|app| replace_bundle(&app, bundle));
+
+    let _ = Command::new("hdiutil").args(["detach", "-quiet"]).arg(&mount).status();
+    result
+}
+
+// ditto keeps the bundle's symlinks, permissions and code signature intact. The copy goes
+// to a sibling first and the old bundle is only deleted once the new one is in place
+fn replace_bundle(app: &Path, bundle: &Path) -> Result<(), String> {
+    let staged = bundle.with_extension("app.new");
+    let previous = bundle.with_extension("app.old");
+    let _ = fs::remove_dir_all(&staged);
+    let _ = fs::remove_dir_all(&previous);
+
+    let status = Command::new("ditto").arg(app).arg(&staged).status().map_err(|e| e.to_string())?;
+    if !status.success() {
+        let _ = fs::remove_dir_all(&staged);
+        return Err("Failed to copy the application into place".to_string());
+    }
+
+    let had_previous = bundle.exists();
+    if had_previous {
+        fs::rename(bundle, &previous).map_err(|e| {
+            let _ = fs::remove_dir_all(&staged);
+            format!("Cannot move {} aside: {}", bundle.display(), e)
+        })?;
+    }
+    if let Err(e) = fs::rename(&staged, bundle) {
+        if had_previous {
+            let _ = fs::rename(&previous, bundle);
+        }
+        let _ = fs::remove_dir_all(&staged);
+        return Err(format!("Cannot move the new application into place: {}", e));
+    }
+    let _ = fs::remove_dir_all(&previous);
+    Ok(())
+}
+
+// What an update of this kind overwrites: the AppImage file, the .app bundle, or the
+// directory the Windows installer put the app in
+fn install_target(kind: AssetKind) -> Result<PathBuf, String> {
+    let exe = env::current_exe().map_err(|e| e.to_string())?;
+    match kind {
+        AssetKind::AppImage => env::var_os("APPIMAGE")
+            .map(PathBuf::from)
+            .ok_or_else(|| "The app is not running from an AppImage".to_string()),
+        AssetKind::Dmg => exe
+            .ancestors()
+            .find(|p| p.extension().is_some_and(|e| e == "app"))
+            .map(|p| p.to_path_buf())
+            .ok_or_else(|| "The app is not running from an .app bundle".to_string()),
+        AssetKind::Nsis | AssetKind::Msi => exe
+            .parent()
+            .map(|p| p.to_path_buf())
+            .ok_or_else(|| "Could not determine the install directory".to_string()),
+        AssetKind::Deb | AssetKind::Rpm => Err("Package installs are managed by the package manager".to_string()),
+    }
+}
+
+fn staging_root(data_dir: &Path) -> PathBuf {
+    data_dir.join("update-staging")
+}
+
+// Where one update is downloaded to: a private directory under the app's data dir, named
+// after the file being fetched so an attempt that ran out of retries resumes the next time.
+// The lock on `.lock` keeps a second instance from writing into it meanwhile; the OS drops
+// the lock on exit
+struct Staging {
+    dir: PathBuf,
+    lock: File,
+}
+
+impl Staging {
+    fn open(data_dir: &Path, url: &str, sha256: &str) -> Result<Self, String> {
+        let key = format!("{:x}", Sha256::digest(format!("{}\n{}", url, sha256.trim())));
+        let dir = staging_root(data_dir).join(&key[..16]);
+        create_private_dir(data_dir, &dir)?;
+        let lock = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(dir.join(".lock"))
+            .map_err(|e| e.to_string())?;
+        lock.try_lock().map_err(|_| "This update is already being downloaded".to_string())?;
+        Ok(Self { dir, lock })
+    }
+
+    // Deletes everything downloaded so far, for a cancelled or unverifiable download
+    fn discard(self) {
+        drop(self.lock);
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+// Creates `dir` for the current user only and refuses one owned by anyone else, so nothing
+// can be swapped into it between verifying an update and installing it
+#[cfg(unix)]
+fn create_private_dir(data_dir: &Path, dir: &Path) -> Result<(), String> {
+    use std::os::unix::fs::{DirBuilderExt, MetadataExt, PermissionsExt};
+
+    fs::DirBuilder::new().recursive(true).mode(0o700).create(dir).map_err(|e| e.to_string())?;
+    let owner = fs::metadata(data_dir).map_err(|e| e.to_string())?.uid();
+    let meta = fs::symlink_metadata(dir).map_err(|e| e.to_string())?;
+    if !meta.is_dir() || meta.uid() != owner {
+        return Err(format!("Refusing to stage the update in {}, which belongs to another user", dir.display()));
+    }
+    fs::set_permissions(dir, fs::Permissions::from_mode(0o700)).map_err(|e| e.to_string())
+}
+
+// The per-user app data dir is already private on Windows
+#[cfg(not(unix))]
+fn create_private_dir(_data_dir: &Path, dir: &Path) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|e| e.to_string())
+}
+
+// True while `path` still names the file `file` was opened from
+#[cfg(unix)]
+fn is_same_file(file: &File, path: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    match (file.metadata(), fs::symlink_metadata(path)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+fn is_same_file(_file: &File, path: &Path) -> bool {
+    path.is_file()
+}
+
+// A successful update leaves its installer behind for the OS to run after the app exits,
+// and an abandoned one its partial download; anything older than a day and not in use goes
+fn remove_stale_staging(data_dir: &Path) {
+    let Ok(entries) = fs::read_dir(staging_root(data_dir)) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let stale = entry
+            .metadata()
+            .and_then(|m| m.modified())
+            .is_ok_and(|modified| modified.elapsed().is_ok_and(|age| age > Duration::from_secs(24 * 60 * 60)));
+        let in_use = File::open(entry.path().join(".lock")).is_ok_and(|lock| lock.try_lock_shared().is_err());
+        if stale && !in_use {
+            let _ = fs::remove_dir_all(entry.path());
+        }
+    }
+}
+
+// The version an update replaced, kept so a broken update can be undone
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateBackup {
+    version: String,
+    kind: AssetKind,
+    // Where the backup is restored to
+    target: String,
+}
+
+// Update state lives with the app's own data, apart from the Node.js versions it manages
+fn data_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path().app_data_dir().map_err(|e| e.to_string())
+}
+
+fn backup_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join("update-backup")
+}
+
+fn read_backup(data_dir: &Path) -> Option<(UpdateBackup, PathBuf)> {
+    let dir = backup_dir(data_dir);
+    let content = fs::read_to_string(dir.join("backup.json")).ok()?;
+    let backup = serde_json::from_str(&content).ok()?;
+    Some((backup, dir.join("app")))
+}
+
+fn copy_dir(from: &Path, to: &Path) -> Result<(), String> {
+    fs::create_dir_all(to).map_err(|e| e.to_string())?;
+    for entry in fs::read_dir(from).map_err(|e| e.to_string())?.flatten() {
+        let dest = to.join(entry.file_name());
+        if entry.file_type().map_err(|e| e.to_string())?.is_dir() {
+            copy_dir(&entry.path(), &dest)?;
+        } else {
+            fs::copy(entry.path(), &dest).map_err(|e| format!("{}: {}", entry.path().display(), e))?;
+        }
+    }
+    Ok(())
+}
+
+// Copies the running version aside before the installer overwrites it. The copy is built
+// next to the old backup and swapped in only when complete, so a failure keeps the old one
+fn backup_current(data_dir: &Path, kind: AssetKind, version: &str) -> Result<(), String> {
+    let dir = backup_dir(data_dir);
+    let target = install_target(kind)?;
+    let staged = dir.with_file_name("update-backup.tmp");
+    let _ = fs::remove_dir_all(&staged);
+    fs::create_dir_all(&staged).map_err(|e| e.to_string())?;
+
+    let copied = match kind {
+        AssetKind::Dmg => replace_bundle(&target, &staged.join("app")),
+        AssetKind::Nsis | AssetKind::Msi => copy_dir(&target, &staged.join("app")),
+        _ => fs::copy(&target, staged.join("app")).map(|_| ()).map_err(|e| e.to_string()),
+    };
+    let backup = UpdateBackup { version: version.to_string(), kind, target: target.to_string_lossy().to_string() };
+    let result = copied
+        .and_then(|_| serde_json::to_string(&backup).map_err(|e| e.to_string()))
+        .and_then(|json| fs::write(staged.join("backup.json"), json).map_err(|e| e.to_string()))
+        .and_then(|_| {
+            let _ = fs::remove_dir_all(&dir);
+            fs::rename(&staged, &dir).map_err(|e| e.to_string())
+        });
+    if result.is_err() {
+        let _ = fs::remove_dir_all(&staged);
+    }
+    result.map_err(|e| format!("Could not back up the current version: {}", e))
+}
+
+// Windows will not overwrite the running executable, so the backup is copied back once
+// this process has exited
+fn restore_install_dir(backup: &Path, target: &Path) -> Result<(), String> {
+    run_after_exit(&format!(
+        "robocopy {} {} /MIR /NFL /NDL /NJH /NJS | Out-Null",
+        ps_quote(&backup.to_string_lossy()),
+        ps_quote(&target.to_string_lossy())
+    ))
+}
+
+// Written just before the app exits for an update and read back by whichever version
+// starts next, so the outcome can be shown after the restart
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PendingUpdate {
+    from_version: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateReport {
+    from_version: String,
+    // The version now running
+    version: String,
+    success: bool,
+    message: Option<String>,
+}
+
+fn pending_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("update-pending.json")
+}
+
+// Exit code of a silent Windows installer, written by the helper that ran it
+fn exit_code_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("update-exit-code")
+}
+
+fn write_pending(data_dir: &Path, from_version: &str) -> Result<(), String> {
+    fs::create_dir_all(data_dir).map_err(|e| e.to_string())?;
+    let _ = fs::remove_file(exit_code_path(data_dir));
+    let path = pending_path(data_dir);
+    let pending = PendingUpdate { from_version: from_version.to_string() };
+    fs::write(&path, serde_json::to_string(&pending).map_err(|e| e.to_string())?).map_err(|e| e.to_string())
+}
+
+// Reports, once, how the update started before the last exit went
+#[tauri::command]
+pub fn take_update_report(app: AppHandle) -> Option<UpdateReport> {
+    let data_dir = data_dir(&app).ok()?;
+    let path = pending_path(&data_dir);
+    let content = fs::read_to_string(&path).ok()?;
+    let _ = fs::remove_file(&path);
+    let pending: PendingUpdate = serde_json::from_str(&content).ok()?;
+
+    let exit_code_file = exit_code_path(&data_dir);
+    let exit_code = fs::read_to_string(&exit_code_file).ok().and_then(|c| c.trim().parse::<i32>().ok());
+    let _ = fs::remove_file(&exit_code_file);
+    let version = app.package_info().version.to_string();
+    // msiexec exits with 3010 when the install succeeded but wants a reboot
+    let message = match exit_code.filter(|c| *c != 0 && *c != 3010) {
+        Some(code) => Some(format!("The installer exited with code {}", code)),
+        None if version == pending.from_version => Some(format!("Still running {}, the update was not installed", version)),
+        None => None,
+    };
+    Some(UpdateReport { from_version: pending.from_version, version, success: message.is_none(), message })
+}
+
+#[tauri::command]
+pub fn get_update_backup(app: AppHandle) -> Option<UpdateBackup> {
+    read_backup(&data_dir(&app).ok()?).map(|(backup, _)| backup)
+}
+
+// Puts the version saved by the last update back in place and restarts into it
+#[tauri::command]
+pub async fn rollback_update(app: AppHandle) -> Result<(), String> {
+    let (backup, app_path) = read_backup(&data_dir(&app)?).ok_or("No previous version is available to roll back to")?;
+    let target = PathBuf::from(&backup.target);
+    println!("Rolling back to {} in {:?}", backup.version, target);
+
+    tauri::async_runtime::spawn_blocking(move || match backup.kind {
+        AssetKind::AppImage => replace_appimage(&app_path, &target).and_then(|_| relaunch(backup.kind, &target)),
+        AssetKind::Dmg => replace_bundle(&app_path, &target).and_then(|_| relaunch(backup.kind, &target)),
+        AssetKind::Nsis | AssetKind::Msi => restore_install_dir(&app_path, &target),
+        AssetKind::Deb | AssetKind::Rpm => Err("Reinstall the previous package with the package manager".to_string()),
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    app.exit(0);
+    Ok(())
+}
+
 #[tauri::command]
-pub async fn install_update(app: AppHandle, state: State<'_, UpdateState>, url: String) -> Result<(), String> {
+pub async fn install_update(
+    app: AppHandle,
+    state: State<'_, UpdateState>,
+    url: String,
+    sha256: String,
+    signature: String,
+    silent: Option<bool>,
+) -> Result<(), String> {
     println!("Starting update download from: {}", url);
-    
+    let file_name = url.split(['?', '#']).next().unwrap_or_default().rsplit('/').next().unwrap_or_default();
+    let kind = AssetKind::from_name(file_name).ok_or_else(|| format!("Unsupported update file: {}", file_name))?;
+    if !preferred_kinds().contains(&kind) {
+        return Err(format!("{} cannot be installed on this system", file_name));
+    }
+
     // Reset cancellation state
     state.is_cancelling.store(false, Ordering::SeqCst);
     let is_cancelling = state.is_cancelling.clone();
 
-    // Use blocking task to avoid blocking the async runtime with file I/O and synchronous download
-    // But since we added blocking feature to reqwest, we can use it inside spawn_blocking
-    
+    let data_dir = data_dir(&app)?;
+    remove_stale_staging(&data_dir);
+    let staging = Staging::open(&data_dir, &url, &sha256)?;
+    let version = app.package_info().version.to_string();
+
     let app_handle = app.clone();
     let result = tauri::async_runtime::spawn_blocking(move || {
-        let mut response = reqwest::blocking::get(&url).map_err(|e| e.to_string())?;
-        
-        let total_size = response.content_length().unwrap_or(0);
-        
-        let mut temp_path = env::temp_dir();
-        temp_path.push("frontend-manager-update.exe");
-        
+        let temp_path = staging.dir.join(format!("update.{}", kind.extension()));
+
         println!("Downloading to: {:?}", temp_path);
-        
-        let mut dest = File::create(&temp_path).map_err(|e| e.to_string())?;
-        
-        let mut buffer = [0; 16384]; // 16KB buffer
-        let mut downloaded: u64 = 0;
-        let mut last_percentage: u64 = 0;
-
-        loop {
-            if is_cancelling.load(Ordering::SeqCst) {
-                 return Err("Update cancelled by user".to_string());
-            }
 
-            let bytes_read = response.read(&mut buffer).map_err(|e| e.to_string())?;
-            if bytes_read == 0 {
-                break;
+        let http = HttpClient::new()?;
+        let mut tracker = ProgressTracker::new();
+        let mut on_progress = |downloaded: u64, total_size: u64| {
+            let total = Some(total_size).filter(|t| *t > 0);
+            if let Some(progress) = tracker.update(Instant::now(), downloaded, total) {
+                let _ = app_handle.emit("download-progress", progress);
             }
-            dest.write_all(&buffer[..bytes_read]).map_err(|e| e.to_string())?;
-            downloaded += bytes_read as u64;
-
-            if total_size > 0 {
-                let percentage = (downloaded as f64 / total_size as f64 * 100.0) as u64;
-                if percentage > last_percentage {
-                    let _ = app_handle.emit("download-progress", percentage);
-                    last_percentage = percentage;
+        };
+
+        // A dropped connection keeps the partial file, so each retry resumes where it stopped
+        let mut attempt = 0;
+        let downloaded = loop {
+            match download(&http, &url, &temp_path, &is_cancelling, &mut on_progress) {
+                Err(e) if e.starts_with("Download interrupted") && attempt < http.retries() && !is_cancelling.load(Ordering::SeqCst) => {
+                    println!("{}, retrying", e);
+                    std::thread::sleep(http::backoff(attempt));
+                    attempt += 1;
                 }
+                result => break result,
             }
+        };
+        // An interrupted download stays for the next attempt to resume. A cancelled one, or a
+        // file that fails verification, is deleted so it can never be launched later
+        match downloaded {
+            Err(e) if is_cancelling.load(Ordering::SeqCst) => {
+                staging.discard();
+                return Err(e);
+            }
+            result => result?,
+        };
+        let file = File::open(&temp_path).map_err(|e| e.to_string())?;
+        if let Err(e) = verify(&file, &sha256, &signature, UPDATE_PUBLIC_KEY) {
+            staging.discard();
+            return Err(e);
         }
-        
-        Ok::<std::path::PathBuf, String>(temp_path)
-    }).await.map_err(|e| e.to_string())??;
-
-    println!("Download complete. Launching installer...");
 
-    // Launch the installer
-    // Using cmd /c start to ensure it runs independently
-    #[cfg(target_os = "windows")]
-    Command::new("cmd")
-        .args(["/C", "start", "", result.to_str().unwrap()])
-        .spawn()
-        .map_err(|e| e.to_string())?;
+        println!("Download verified. Installing {:?} update...", kind);
+        // Package managers keep their own record of what was installed
+        if !matches!(kind, AssetKind::Deb | AssetKind::Rpm) {
+            backup_current(&data_dir, kind, &version)?;
+        }
+        write_pending(&data_dir, &version)?;
+        if !is_same_file(&file, &temp_path) {
+            let _ = fs::remove_file(pending_path(&data_dir));
+            return Err("The update file changed after it was verified".to_string());
+        }
+        // Package installs wait for pkexec and the package manager
+        launch_installer(kind, &temp_path, silent.unwrap_or(false), &data_dir).inspect_err(|_| {
+            let _ = fs::remove_file(pending_path(&data_dir));
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    result?;
 
-    #[cfg(not(target_os = "windows"))]
-    Command::new(result)
-        .spawn()
-        .map_err(|e| e.to_string())?;
-        
     println!("Installer launched. Exiting app.");
     app.exit(0);
-    
+
     Ok(())
 }
+
+#[cfg(all(test, not(target_os = "windows")))]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+    use std::sync::Mutex;
+    use std::thread;
+
+    fn test_client() -> HttpClient {
+        HttpClient::from_settings(&http::HttpSettings { proxy: "none".to_string(), retries: 0, ..Default::default() }).unwrap()
+    }
+
+    // Test key pair generated for these fixtures only; the payload is PAYLOAD_LINE repeated 64 times
+    const PUBLIC_KEY: &str = "RWQjQxo9uNM6UHxz+gQulmboQ/hXyhZyCZCN6JTide9iCr/DgahpZ5mJ";
+    const SIGNATURE: &str = "untrusted comment: signature from minisign secret key
+RUQjQxo9uNM6UFHNYUANUhKu+keIQBaPz4BUhVwJS5y1QIo/cXS47PiMky189IJYQ3lQkre/9Fk5WMvipAqaCGKFX7Z8rT2tbwY=
+trusted comment: timestamp:1760000000\tfile:frontend-manager-update.bin
+eYDZhkz7rxdCy9NHoSwsUxekSlNOVhY1I55lqkzFpbnUakPHSfnLYJy3o7DBvviTC5bXBCI2Khcr7XxVitUXBA==
+";
+    const SHA256: &str = "5e39c542b984e67587a78ce79b4c15f47743c4f424b56b33786f870c2bd79bb7";
+    const PAYLOAD_LINE: &[u8] = b"frontend-manager update payload\n";
+
+    fn payload() -> Vec<u8> {
+        PAYLOAD_LINE.repeat(64)
+    }
+
+    const ETAG_VALUE: &str = "\"v1\"";
+
+    // Serves one body at any path until the test exits. With `ranges` it honours
+    // "Range: bytes=N-" when If-Range matches its ETag; returns the URL and the Range
+    // headers it received
+    fn serve(body: Vec<u8>, ranges: bool) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_by_server = seen.clone();
+        thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let (mut range, mut if_range) = (None, None);
+                loop {
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).unwrap() == 0 || line == "\r\n" {
+                        break;
+                    }
+                    if let Some((name, value)) = line.trim_end().split_once(": ") {
+                        match name.to_ascii_lowercase().as_str() {
+                            "range" => range = Some(value.to_string()),
+                            "if-range" => if_range = Some(value.to_string()),
+                            _ => {}
+                        }
+                    }
+                }
+                if let Some(range) = &range {
+                    seen_by_server.lock().unwrap().push(range.clone());
+                }
+
+                let start = range
+                    .as_deref()
+                    .and_then(|r| r.strip_prefix("bytes=")?.strip_suffix('-')?.parse::<usize>().ok())
+                    .filter(|_| ranges && if_range.as_deref() == Some(ETAG_VALUE));
+                let mut response = match start {
+                    Some(start) if start >= body.len() => {
+                        let head = format!(
+                            "HTTP/1.1 416 Range Not Satisfiable\r\nContent-Range: bytes */{}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                            body.len()
+                        );
+                        let _ = stream.write_all(head.as_bytes());
+                        continue;
+                    }
+                    Some(start) => format!(
+                        "HTTP/1.1 206 Partial Content\r\nContent-Range: bytes {}-{}/{}\r\nContent-Length: {}\r\nETag: {}\r\nConnection: close\r\n\r\n",
+                        start,
+                        body.len() - 1,
+                        body.len(),
+                        body.len() - start,
+                        ETAG_VALUE
+                    ),
+                    None => format!(
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nETag: {}\r\nConnection: close\r\n\r\n",
+                        body.len(),
+                        ETAG_VALUE
+                    ),
+                }
+                .into_bytes();
+                response.extend_from_slice(&body[start.unwrap_or(0)..]);
+                let _ = stream.write_all(&response);
+            }
+        });
+        (format!("http://{}/update.bin", addr), seen)
+    }
+
+    // Leaves a .part file as an interrupted download of `url` would
+    fn interrupted(dest: &Path, url: &str, bytes: &[u8], etag: &str) {
+        let (part, meta) = partial_paths(dest);
+        fs::write(part, bytes).unwrap();
+        let previous = PartialDownload { url: url.to_string(), etag: Some(etag.to_string()), last_modified: None };
+        fs::write(meta, serde_json::to_string(&previous).unwrap()).unwrap();
+    }
+
+    fn temp_file(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("fp-update-{}-{}", name, std::process::id()))
+    }
+
+    fn fetch(body: Vec<u8>, name: &str) -> (PathBuf, String) {
+        let dest = temp_file(name);
+        let actual = download(&test_client(), &serve(body, false).0, &dest, &AtomicBool::new(false), &mut |_, _| {}).unwrap();
+        (dest, actual)
+    }
+
+    #[test]
+    fn accepts_signed_update() {
+        let (dest, actual) = fetch(payload(), "ok");
+        assert_eq!(actual, SHA256);
+        verify(&File::open(&dest).unwrap(), SHA256, SIGNATURE, PUBLIC_KEY).unwrap();
+        let _ = fs::remove_file(dest);
+    }
+
+    #[test]
+    fn rejects_tampered_file() {
+        let mut tampered = payload();
+        tampered[0] ^= 1;
+        let (dest, _) = fetch(tampered, "tampered");
+        let err = verify(&File::open(&dest).unwrap(), SHA256, SIGNATURE, PUBLIC_KEY).unwrap_err();
+        assert!(err.contains("Checksum mismatch"));
+        let _ = fs::remove_file(dest);
+    }
+
+    #[test]
+    fn rejects_tampered_file_with_matching_checksum() {
+        // An attacker who controls the manifest can publish a matching digest, but not a signature
+        let mut tampered = payload();
+        tampered[0] ^= 1;
+        let (dest, actual) = fetch(tampered, "resigned");
+        let err = verify(&File::open(&dest).unwrap(), &actual, SIGNATURE, PUBLIC_KEY).unwrap_err();
+        assert!(err.contains("signature verification failed"));
+        let _ = fs::remove_file(dest);
+    }
+
+    #[test]
+    fn rejects_signature_from_other_key() {
+        let (dest, _) = fetch(payload(), "otherkey");
+        let other_key = "RWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3";
+        assert!(verify(&File::open(&dest).unwrap(), SHA256, SIGNATURE, other_key).is_err());
+        let _ = fs::remove_file(dest);
+    }
+
+    #[test]
+    fn stages_in_a_private_directory() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let data_dir = temp_file("staging");
+        let staging = Staging::open(&data_dir, "https://example.com/app.deb", SHA256).unwrap();
+        assert!(staging.dir.starts_with(&data_dir));
+        assert_eq!(fs::metadata(&staging.dir).unwrap().permissions().mode() & 0o777, 0o700);
+        // The same download resumes in the same place, but not while it is locked
+        assert!(Staging::open(&data_dir, "https://example.com/app.deb", SHA256).is_err());
+        staging.discard();
+        let _ = fs::remove_dir_all(data_dir);
+    }
+
+    #[test]
+    fn refuses_staging_through_a_symlink() {
+        let data_dir = temp_file("symlink");
+        let elsewhere = temp_file("symlink-target");
+        fs::create_dir_all(staging_root(&data_dir)).unwrap();
+        fs::create_dir_all(&elsewhere).unwrap();
+        let key = format!("{:x}", Sha256::digest(format!("{}\n{}", "https://example.com/app.deb", SHA256)));
+        std::os::unix::fs::symlink(&elsewhere, staging_root(&data_dir).join(&key[..16])).unwrap();
+        assert!(Staging::open(&data_dir, "https://example.com/app.deb", SHA256).is_err());
+        let _ = fs::remove_dir_all(data_dir);
+        let _ = fs::remove_dir_all(elsewhere);
+    }
+
+    #[test]
+    fn resumes_interrupted_download() {
+        let (url, seen) = serve(payload(), true);
+        let dest = temp_file("resume");
+        interrupted(&dest, &url, &payload()[..1000], ETAG_VALUE);
+
+        let actual = download(&test_client(), &url, &dest, &AtomicBool::new(false), &mut |_, _| {}).unwrap();
+
+        assert_eq!(actual, SHA256);
+        assert_eq!(*seen.lock().unwrap(), vec!["bytes=1000-".to_string()]);
+        assert_eq!(fs::read(&dest).unwrap(), payload());
+        let (part, meta) = partial_paths(&dest);
+        assert!(!part.exists() && !meta.exists());
+        let _ = fs::remove_file(dest);
+    }
+
+    #[test]
+    fn restarts_when_part_is_already_complete() {
+        let (url, seen) = serve(payload(), true);
+        let dest = temp_file("complete");
+        interrupted(&dest, &url, &payload(), ETAG_VALUE);
+
+        let actual = download(&test_client(), &url, &dest, &AtomicBool::new(false), &mut |_, _| {}).unwrap();
+
+        assert_eq!(actual, SHA256);
+        assert_eq!(*seen.lock().unwrap(), vec![format!("bytes={}-", payload().len())]);
+        assert_eq!(fs::read(&dest).unwrap(), payload());
+        let _ = fs::remove_file(dest);
+    }
+
+    #[test]
+    fn restarts_when_server_ignores_range() {
+        let (url, seen) = serve(payload(), false);
+        let dest = temp_file("norange");
+        interrupted(&dest, &url, b"stale bytes from an older attempt", ETAG_VALUE);
+
+        let actual = download(&test_client(), &url, &dest, &AtomicBool::new(false), &mut |_, _| {}).unwrap();
+
+        assert_eq!(actual, SHA256);
+        assert_eq!(seen.lock().unwrap().len(), 1);
+        assert_eq!(fs::read(&dest).unwrap(), payload());
+        let _ = fs::remove_file(dest);
+    }
+
+    #[test]
+    fn restarts_when_file_changed() {
+        let (url, _) = serve(payload(), true);
+        let dest = temp_file("changed");
+        interrupted(&dest, &url, b"bytes of a previous release", "\"v0\"");
+
+        let actual = download(&test_client(), &url, &dest, &AtomicBool::new(false), &mut |_, _| {}).unwrap();
+
+        assert_eq!(actual, SHA256);
+        assert_eq!(fs::read(&dest).unwrap(), payload());
+        let _ = fs::remove_file(dest);
+    }
+
+    #[test]
+    fn picks_newest_release_for_channel() {
+        let manifest = br#"{
+            "minimumVersion": "0.1.5",
+            "releases": [
+                { "version": "0.1.11", "notes": "stable fixes" },
+                { "version": "0.2.0-beta.1", "channel": "beta", "notes": "preview" },
+                { "version": "0.1.9" }
+            ]
+        }"#;
+        let (url, _) = serve(manifest.to_vec(), false);
+
+        let stable = check("0.1.4", "stable", &url).unwrap();
+        assert!(stable.available && stable.required);
+        assert_eq!(stable.version.as_deref(), Some("0.1.11"));
+        assert_eq!(stable.notes.as_deref(), Some("stable fixes"));
+
+        let beta = check("0.1.10", "beta", &url).unwrap();
+        assert_eq!(beta.version.as_deref(), Some("0.2.0-beta.1"));
+        assert!(!beta.required);
+
+        assert!(!check("0.1.11", "stable", &url).unwrap().available);
+    }
+
+    #[test]
+    fn throttles_progress_and_estimates_time_left() {
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        let mut tracker = ProgressTracker::new();
+
+        let first = tracker.update(at(0), 0, Some(4_000_000)).unwrap();
+        assert_eq!(first.eta_seconds, None);
+        assert!(tracker.update(at(100), 100_000, Some(4_000_000)).is_none());
+
+        let second = tracker.update(at(1000), 1_000_000, Some(4_000_000)).unwrap();
+        assert_eq!(second.bytes_per_second, 1_000_000);
+        assert_eq!(second.eta_seconds, Some(3));
+
+        // The last chunk is always reported, however soon it follows
+        let done = tracker.update(at(1010), 4_000_000, Some(4_000_000)).unwrap();
+        assert_eq!(done.eta_seconds, Some(0));
+
+        // Without a Content-Length there is no estimate, but progress still flows
+        let mut tracker = ProgressTracker::new();
+        tracker.update(at(0), 0, None);
+        let unknown = tracker.update(at(500), 500_000, None).unwrap();
+        assert_eq!((unknown.total, unknown.eta_seconds, unknown.bytes_per_second), (None, None, 1_000_000));
+    }
+
+    #[test]
+    fn embedded_public_key_is_valid() {
+        assert!(PublicKey::from_base64(UPDATE_PUBLIC_KEY).is_ok());
+    }
+}
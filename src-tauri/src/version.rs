@@ -0,0 +1,434 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+// Semver versions and npm-style ranges ("^1.2", "~1.2.3", ">=1 <2", "1.x || 2", "1 - 2.3")
+// implemented to npm's rules rather than Cargo's, since the inputs come from package.json.
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    pub pre: Vec<String>,
+}
+
+impl Version {
+    pub fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self { major, minor, patch, pre: Vec::new() }
+    }
+
+    // Accepts "v18.17.0", "18.17.0", "=1.2.3-beta.1+build"
+    pub fn parse(input: &str) -> Option<Self> {
+        let s = input.trim().trim_start_matches('=').trim_start_matches('v');
+        let s = s.split('+').next().unwrap_or_default();
+        let (core, pre) = match s.split_once('-') {
+            Some((core, pre)) => (core, pre.split('.').map(|p| p.to_string()).collect()),
+            None => (s, Vec::new()),
+        };
+        let mut parts = core.split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next()?.parse().ok()?;
+        let patch = parts.next()?.parse().ok()?;
+        // Identifiers are [0-9A-Za-z-] only, so a parsed version is always safe in a path
+        let valid = |p: &String| !p.is_empty() && p.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+        if parts.next().is_some() || !pre.iter().all(valid) {
+            return None;
+        }
+        Some(Self { major, minor, patch, pre })
+    }
+
+    pub fn is_prerelease(&self) -> bool {
+        !self.pre.is_empty()
+    }
+
+    fn same_core(&self, other: &Version) -> bool {
+        self.major == other.major && self.minor == other.minor && self.patch == other.patch
+    }
+
+    // The lowest version of the next release, used as an exclusive upper bound ("<2.0.0-0")
+    fn lowest(major: u64, minor: u64, patch: u64) -> Self {
+        Self { major, minor, patch, pre: vec!["0".to_string()] }
+    }
+}
+
+fn compare_identifiers(a: &str, b: &str) -> Ordering {
+    match (a.parse::<u64>(), b.parse::<u64>()) {
+        (Ok(x), Ok(y)) => x.cmp(&y),
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        (Err(_), Err(_)) => a.cmp(b),
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.major
+            .cmp(&other.major)
+            .then(self.minor.cmp(&other.minor))
+            .then(self.patch.cmp(&other.patch))
+            .then_with(|| match (self.pre.is_empty(), other.pre.is_empty()) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => {
+                    for (a, b) in self.pre.iter().zip(other.pre.iter()) {
+                        let ord = compare_identifiers(a, b);
+                        if ord != Ordering::Equal {
+                            return ord;
+                        }
+                    }
+                    self.pre.len().cmp(&other.pre.len())
+                }
+            })
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if !self.pre.is_empty() {
+            write!(f, "-{}", self.pre.join("."))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+}
+
+#[derive(Clone, Debug)]
+struct Comparator {
+    op: Op,
+    version: Version,
+}
+
+impl Comparator {
+    fn matches(&self, v: &Version) -> bool {
+        match self.op {
+            Op::Eq => v == &self.version,
+            Op::Gt => v > &self.version,
+            Op::Gte => v >= &self.version,
+            Op::Lt => v < &self.version,
+            Op::Lte => v <= &self.version,
+        }
+    }
+}
+
+// A version with missing or wildcard components, e.g. "1", "1.2", "1.x", "*"
+struct Partial {
+    major: Option<u64>,
+    minor: Option<u64>,
+    patch: Option<u64>,
+    pre: Vec<String>,
+}
+
+impl Partial {
+    fn parse(input: &str) -> Option<Self> {
+        let s = input.trim().trim_start_matches('v');
+        let s = s.split('+').next().unwrap_or_default();
+        let (core, pre) = match s.split_once('-') {
+            Some((core, pre)) => (core, pre.split('.').map(|p| p.to_string()).collect()),
+            None => (s, Vec::new()),
+        };
+        let mut parts = core.split('.');
+        let mut next = || -> Option<Option<u64>> {
+            match parts.next() {
+                None | Some("x") | Some("X") | Some("*") => Some(None),
+                Some(p) => p.parse().ok().map(Some),
+            }
+        };
+        let major = if core.is_empty() { None } else { next()? };
+        let minor = if major.is_some() { next()? } else { None };
+        let patch = if minor.is_some() { next()? } else { None };
+        Some(Self { major, minor, patch, pre })
+    }
+
+    fn floor(&self) -> Version {
+        Version {
+            major: self.major.unwrap_or(0),
+            minor: self.minor.unwrap_or(0),
+            patch: self.patch.unwrap_or(0),
+            pre: self.pre.clone(),
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        self.patch.is_some()
+    }
+
+    // Exclusive upper bound of an x-range: "1" -> 2.0.0-0, "1.2" -> 1.3.0-0
+    fn x_ceiling(&self) -> Option<Version> {
+        match (self.major, self.minor, self.patch) {
+            (Some(major), None, _) => Some(Version::lowest(major + 1, 0, 0)),
+            (Some(major), Some(minor), None) => Some(Version::lowest(major, minor + 1, 0)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Range {
+    sets: Vec<Vec<Comparator>>,
+}
+
+impl Range {
+    pub fn parse(input: &str) -> Option<Self> {
+        let mut sets = Vec::new();
+        for part in input.split("||") {
+            sets.push(parse_set(part.trim())?);
+        }
+        Some(Self { sets })
+    }
+
+    pub fn matches(&self, v: &Version) -> bool {
+        self.sets.iter().any(|set| {
+            if !set.iter().all(|c| c.matches(v)) {
+                return false;
+            }
+            // Pre-releases only satisfy a range that mentions a pre-release of the same x.y.z
+            !v.is_prerelease() || set.iter().any(|c| c.version.is_prerelease() && c.version.same_core(v))
+        })
+    }
+}
+
+fn cmp(op: Op, version: Version) -> Comparator {
+    Comparator { op, version }
+}
+
+fn parse_set(input: &str) -> Option<Vec<Comparator>> {
+    let mut out = Vec::new();
+
+    // Hyphen range: "1.2 - 2.3.4"
+    if let Some((from, to)) = input.split_once(" - ") {
+        let from = Partial::parse(from)?;
+        let to = Partial::parse(to)?;
+        out.push(cmp(Op::Gte, from.floor()));
+        if to.major.is_some() {
+            match to.x_ceiling() {
+                Some(ceiling) => out.push(cmp(Op::Lt, ceiling)),
+                None => out.push(cmp(Op::Lte, to.floor())),
+            }
+        }
+        return Some(out);
+    }
+
+    // Allow "> 1.2" and ">=1 <2" alike by gluing operators to the following token
+    let mut tokens = Vec::new();
+    let mut pending = String::new();
+    for token in input.split_whitespace() {
+        if token.chars().all(|c| matches!(c, '<' | '>' | '=' | '~' | '^')) {
+            pending.push_str(token);
+        } else {
+            tokens.push(format!("{}{}", pending, token));
+            pending.clear();
+        }
+    }
+    if tokens.is_empty() {
+        tokens.push("*".to_string());
+    }
+
+    for token in tokens {
+        let op_len = token.find(|c: char| !matches!(c, '<' | '>' | '=' | '~' | '^')).unwrap_or(token.len());
+        let (op, rest) = token.split_at(op_len);
+        let p = Partial::parse(rest)?;
+
+        match op {
+            "" | "=" => {
+                if p.is_full() {
+                    out.push(cmp(Op::Eq, p.floor()));
+                } else if let Some(ceiling) = p.x_ceiling() {
+                    out.push(cmp(Op::Gte, p.floor()));
+                    out.push(cmp(Op::Lt, ceiling));
+                } else {
+                    out.push(cmp(Op::Gte, Version::new(0, 0, 0)));
+                }
+            }
+            "~" | "~>" => {
+                let major = p.major.unwrap_or(0);
+                out.push(cmp(Op::Gte, p.floor()));
+                match (p.major, p.minor) {
+                    (None, _) => {}
+                    (Some(_), None) => out.push(cmp(Op::Lt, Version::lowest(major + 1, 0, 0))),
+                    (Some(_), Some(minor)) => out.push(cmp(Op::Lt, Version::lowest(major, minor + 1, 0))),
+                }
+            }
+            "^" => {
+                out.push(cmp(Op::Gte, p.floor()));
+                let ceiling = match (p.major, p.minor, p.patch) {
+                    (None, _, _) => None,
+                    (Some(major), _, _) if major > 0 => Some(Version::lowest(major + 1, 0, 0)),
+                    (Some(_), None, _) => Some(Version::lowest(1, 0, 0)),
+                    (Some(_), Some(minor), _) if minor > 0 => Some(Version::lowest(0, minor + 1, 0)),
+                    (Some(_), Some(_), None) => Some(Version::lowest(0, 1, 0)),
+                    (Some(_), Some(_), Some(patch)) => Some(Version::lowest(0, 0, patch + 1)),
+                };
+                if let Some(ceiling) = ceiling {
+                    out.push(cmp(Op::Lt, ceiling));
+                }
+            }
+            ">" => match p.x_ceiling() {
+                Some(ceiling) => out.push(cmp(Op::Gte, ceiling)),
+                None if p.major.is_none() => out.push(cmp(Op::Lt, Version::lowest(0, 0, 0))),
+                None => out.push(cmp(Op::Gt, p.floor())),
+            },
+            ">=" => out.push(cmp(Op::Gte, p.floor())),
+            "<" => out.push(cmp(Op::Lt, if p.is_full() { p.floor() } else { Version::lowest(p.major.unwrap_or(0), p.minor.unwrap_or(0), 0) })),
+            "<=" => match p.x_ceiling() {
+                Some(ceiling) => out.push(cmp(Op::Lt, ceiling)),
+                None if p.major.is_none() => out.push(cmp(Op::Gte, Version::new(0, 0, 0))),
+                None => out.push(cmp(Op::Lte, p.floor())),
+            },
+            _ => return None,
+        }
+    }
+
+    Some(out)
+}
+
+// A Node.js version as users type it, or as found in .nvmrc and engines.node:
+// "18", "v20.11.1", "^20.1", "lts/*", "lts/hydrogen", "node" or "latest"
+#[derive(Clone, Debug)]
+pub enum NodeSpec {
+    Latest,
+    // An LTS codename, None meaning any LTS line
+    Lts(Option<String>),
+    Range(Range),
+}
+
+impl NodeSpec {
+    pub fn parse(input: &str) -> Option<Self> {
+        let s = input.trim();
+        let lower = s.to_ascii_lowercase();
+        match lower.as_str() {
+            "" => None,
+            "node" | "latest" | "current" | "stable" => Some(Self::Latest),
+            "lts" | "lts/*" => Some(Self::Lts(None)),
+            _ => match lower.strip_prefix("lts/") {
+                Some(name) if name.chars().all(|c| c.is_ascii_alphabetic()) => Some(Self::Lts(Some(name.to_string()))),
+                Some(_) => None,
+                None => Range::parse(s).map(Self::Range),
+            },
+        }
+    }
+
+    // `lts` is the release line's codename, when known
+    pub fn matches(&self, v: &Version, lts: Option<&str>) -> bool {
+        match self {
+            Self::Latest => !v.is_prerelease(),
+            Self::Lts(None) => lts.is_some() && !v.is_prerelease(),
+            Self::Lts(Some(name)) => lts.is_some_and(|l| l.eq_ignore_ascii_case(name)),
+            Self::Range(range) => range.matches(v),
+        }
+    }
+
+    pub fn needs_lts(&self) -> bool {
+        matches!(self, Self::Lts(_))
+    }
+
+    // Highest candidate satisfying the spec
+    pub fn resolve<'a, I>(&self, candidates: I) -> Option<Version>
+    where
+        I: IntoIterator<Item = (&'a str, Option<&'a str>)>,
+    {
+        candidates
+            .into_iter()
+            .filter_map(|(version, lts)| Version::parse(version).filter(|v| self.matches(v, lts)))
+            .max()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn satisfies(range: &str, version: &str) -> bool {
+        Range::parse(range).unwrap().matches(&Version::parse(version).unwrap())
+    }
+
+    #[test]
+    fn caret_ranges() {
+        assert!(satisfies("^1.2.3", "1.9.0"));
+        assert!(!satisfies("^1.2.3", "2.0.0"));
+        assert!(!satisfies("^1.2.3", "1.2.2"));
+        assert!(satisfies("^0.2.3", "0.2.9"));
+        assert!(!satisfies("^0.2.3", "0.3.0"));
+        assert!(!satisfies("^0.0.3", "0.0.4"));
+    }
+
+    #[test]
+    fn tilde_ranges() {
+        assert!(satisfies("~1.2.3", "1.2.9"));
+        assert!(!satisfies("~1.2.3", "1.3.0"));
+        assert!(satisfies("~1", "1.9.9"));
+        assert!(!satisfies("~1", "2.0.0"));
+    }
+
+    #[test]
+    fn x_ranges() {
+        assert!(satisfies("1.x", "1.4.0"));
+        assert!(!satisfies("1.x", "2.0.0"));
+        assert!(satisfies("1.2.*", "1.2.7"));
+        assert!(!satisfies("1.2.*", "1.3.0"));
+        assert!(satisfies("*", "22.0.0"));
+        assert!(satisfies("", "22.0.0"));
+    }
+
+    #[test]
+    fn comparator_sets_and_unions() {
+        assert!(satisfies(">=18 <20", "19.1.0"));
+        assert!(!satisfies(">=18 <20", "20.0.0"));
+        assert!(satisfies("^16 || ^18", "18.2.0"));
+        assert!(!satisfies("^16 || ^18", "17.0.0"));
+        assert!(satisfies("> 1.2", "1.3.0"));
+        assert!(!satisfies("> 1.2", "1.2.9"));
+    }
+
+    #[test]
+    fn hyphen_ranges() {
+        assert!(satisfies("1.2.3 - 2.3.4", "2.3.4"));
+        assert!(!satisfies("1.2.3 - 2.3.4", "2.3.5"));
+        assert!(satisfies("1.2 - 2.3", "2.3.9"));
+        assert!(!satisfies("1.2 - 2.3", "2.4.0"));
+        assert!(!satisfies("1.2 - 2.3", "1.1.9"));
+    }
+
+    #[test]
+    fn prerelease_ordering() {
+        let order = ["1.0.0-alpha", "1.0.0-alpha.1", "1.0.0-alpha.beta", "1.0.0-beta", "1.0.0-beta.2", "1.0.0-beta.11", "1.0.0-rc.1", "1.0.0"];
+        for pair in order.windows(2) {
+            assert!(Version::parse(pair[0]) < Version::parse(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn prereleases_only_match_ranges_that_name_them() {
+        assert!(!satisfies("^20", "21.0.0-rc.1"));
+        assert!(!satisfies(">=20.0.0", "20.1.0-rc.1"));
+        assert!(satisfies(">=20.1.0-rc.0", "20.1.0-rc.1"));
+        assert!(!satisfies(">=20.1.0-rc.0", "20.2.0-rc.1"));
+    }
+
+    #[test]
+    fn resolves_lts() {
+        let releases = [("v22.1.0", None), ("v20.12.2", Some("Iron")), ("v20.11.1", Some("Iron")), ("v18.20.2", Some("Hydrogen"))];
+        let resolve = |spec: &str| NodeSpec::parse(spec).unwrap().resolve(releases.iter().map(|(v, l)| (*v, *l))).map(|v| v.to_string());
+        assert_eq!(resolve("lts/*").as_deref(), Some("20.12.2"));
+        assert_eq!(resolve("lts/hydrogen").as_deref(), Some("18.20.2"));
+        assert_eq!(resolve("node").as_deref(), Some("22.1.0"));
+        assert_eq!(resolve("^20.11").as_deref(), Some("20.12.2"));
+        assert_eq!(resolve("lts/gallium"), None);
+        assert!(NodeSpec::parse("lts/../x").is_none());
+    }
+}
@@ -4,7 +4,7 @@ import { getVersion } from '@tauri-apps/api/app';
 import { open as openDialogFn, save as saveDialogFn } from '@tauri-apps/plugin-dialog';
 import { openUrl as openUrlFn } from '@tauri-apps/plugin-opener';
 import { readTextFile as readTextFileFn, writeTextFile as writeTextFileFn } from '@tauri-apps/plugin-fs';
-import type { PlatformAPI, ProjectInfo } from '../types';
+import type { DownloadProgress, InstallResult, InstallStatus, PlatformAPI, ProjectInfo, UninstallResult, UpdateBackup, UpdateInfo, UpdateReport } from '../types';
 import type { NodeVersion } from '../../types';
 
 import { getCurrentWindow } from '@tauri-apps/api/window';
@@ -17,12 +17,12 @@ export class TauriAdapter implements PlatformAPI {
         return invoke('get_nvm_list');
     }
     
-    async installNode(version: string): Promise<string> {
+    async installNode(version: string): Promise<InstallResult> {
         return invoke('install_node', { version });
     }
     
-    async uninstallNode(version: string): Promise<string> {
-        return invoke('uninstall_node', { version });
+    async uninstallNode(version: string, force = false): Promise<UninstallResult> {
+        return invoke('uninstall_node', { version, force });
     }
     
     async useNode(version: string): Promise<string> {
@@ -42,6 +42,10 @@ export class TauriAdapter implements PlatformAPI {
         return invoke('scan_project', { path });
     }
 
+    async checkInstallStatus(path: string): Promise<InstallStatus> {
+        return invoke('check_install_status', { path });
+    }
+
     // Runner
     async runProjectCommand(id: string, path: string, script: string, packageManager: string, nodePath: string): Promise<void> {
         return invoke('run_project_command', { id, path, script, packageManager, nodePath });
@@ -51,6 +55,10 @@ export class TauriAdapter implements PlatformAPI {
         return invoke('stop_project_command', { id });
     }
 
+    async installDependencies(id: string, path: string, packageManager: string, nodePath: string): Promise<void> {
+        return invoke('install_dependencies', { id, path, packageManager, nodePath });
+    }
+
     // System / Shell
     async openInEditor(path: string, editor?: string): Promise<void> {
         return invoke('open_in_editor', { path, editor });
@@ -91,13 +99,29 @@ export class TauriAdapter implements PlatformAPI {
     }
 
     // Updater
-    async installUpdate(url: string): Promise<void> {
-        return invoke('install_update', { url });
+    async checkForUpdate(channel?: string): Promise<UpdateInfo | null> {
+        return invoke('check_for_update', { channel });
+    }
+
+    async installUpdate(url: string, sha256: string, signature: string, silent = false): Promise<void> {
+        return invoke('install_update', { url, sha256, signature, silent });
     }
     
     async cancelUpdate(): Promise<void> {
         return invoke('cancel_update');
     }
+
+    async takeUpdateReport(): Promise<UpdateReport | null> {
+        return invoke('take_update_report');
+    }
+
+    async getUpdateBackup(): Promise<UpdateBackup | null> {
+        return invoke('get_update_backup');
+    }
+
+    async rollbackUpdate(): Promise<void> {
+        return invoke('rollback_update');
+    }
     
     async getAppVersion(): Promise<string> {
         return getVersion();
@@ -119,14 +143,14 @@ export class TauriAdapter implements PlatformAPI {
         });
     }
 
-    async onProjectExit(callback: (payload: { id: string }) => void): Promise<() => void> {
+    async onProjectExit(callback: (payload: { id: string; success?: boolean }) => void): Promise<() => void> {
         return listen<any>('project-exit', (event) => {
             callback(event.payload);
         });
     }
     
-    async onDownloadProgress(callback: (percentage: number) => void): Promise<() => void> {
-        return listen<number>('download-progress', (event) => {
+    async onDownloadProgress(callback: (progress: DownloadProgress) => void): Promise<() => void> {
+        return listen<DownloadProgress>('download-progress', (event) => {
             callback(event.payload);
         });
     }
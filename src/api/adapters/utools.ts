@@ -1,4 +1,4 @@
-import type { PlatformAPI, ProjectInfo } from '../types';
+import type { DownloadProgress, InstallResult, InstallStatus, PlatformAPI, ProjectInfo, UninstallResult, UpdateBackup, UpdateInfo, UpdateReport } from '../types';
 import type { NodeVersion } from '../../types';
 
 // Declare global interface for uTools services
@@ -20,18 +20,25 @@ export class UToolsAdapter implements PlatformAPI {
   }
 
   getNvmList(): Promise<NodeVersion[]> { return this.service.getNvmList(); }
-  installNode(version: string): Promise<string> { return this.service.installNode(version); }
-  uninstallNode(version: string): Promise<string> { return this.service.uninstallNode(version); }
+  installNode(version: string): Promise<InstallResult> { return this.service.installNode(version); }
+  uninstallNode(version: string, force?: boolean): Promise<UninstallResult> { return this.service.uninstallNode(version, force); }
   useNode(version: string): Promise<string> { return this.service.useNode(version); }
   getSystemNodePath(): Promise<string> { return this.service.getSystemNodePath(); }
   getNodeVersion(path: string): Promise<string> { return this.service.getNodeVersion(path); }
 
   scanProject(path: string): Promise<ProjectInfo> { return this.service.scanProject(path); }
+  // No install check under uTools, so runs are never held up by it
+  checkInstallStatus(_path: string): Promise<InstallStatus> {
+    return Promise.resolve({ state: 'ok', packageManager: null, lockfile: null, reason: null, installCommand: null });
+  }
 
   runProjectCommand(id: string, path: string, script: string, packageManager: string, nodePath: string): Promise<void> {
     return this.service.runProjectCommand(id, path, script, packageManager, nodePath);
   }
   stopProjectCommand(id: string): Promise<void> { return this.service.stopProjectCommand(id); }
+  installDependencies(_id: string, _path: string, _packageManager: string, _nodePath: string): Promise<void> {
+    return Promise.reject(new Error('Installing dependencies is not available in uTools'));
+  }
 
   openInEditor(path: string, editor?: string): Promise<void> { return this.service.openInEditor(path, editor); }
   openFolder(path: string): Promise<void> { return this.service.openFolder(path); }
@@ -42,8 +49,15 @@ export class UToolsAdapter implements PlatformAPI {
   readTextFile(path: string): Promise<string> { return this.service.readTextFile(path); }
   writeTextFile(path: string, content: string): Promise<void> { return this.service.writeTextFile(path, content); }
 
-  installUpdate(url: string): Promise<void> { return this.service.installUpdate(url); }
+  // uTools updates plugins itself
+  checkForUpdate(channel?: string): Promise<UpdateInfo | null> {
+    return this.service.checkForUpdate ? this.service.checkForUpdate(channel) : Promise.resolve(null);
+  }
+  installUpdate(url: string, sha256: string, signature: string, silent?: boolean): Promise<void> { return this.service.installUpdate(url, sha256, signature, silent); }
   cancelUpdate(): Promise<void> { return this.service.cancelUpdate ? this.service.cancelUpdate() : Promise.resolve(); }
+  takeUpdateReport(): Promise<UpdateReport | null> { return Promise.resolve(null); }
+  getUpdateBackup(): Promise<UpdateBackup | null> { return Promise.resolve(null); }
+  rollbackUpdate(): Promise<void> { return Promise.reject(new Error('Rollback is not available in uTools')); }
   getAppVersion(): Promise<string> { return this.service.getAppVersion(); }
 
   openDialog(options: any): Promise<string | string[] | null> { return this.service.openDialog(options); }
@@ -56,7 +70,7 @@ export class UToolsAdapter implements PlatformAPI {
     return this.service.onProjectExit(callback);
   }
 
-  async onDownloadProgress(callback: (percentage: number) => void): Promise<() => void> {
+  async onDownloadProgress(callback: (progress: DownloadProgress) => void): Promise<() => void> {
       return this.service.onDownloadProgress(callback);
   }
 
@@ -6,21 +6,96 @@ export interface ProjectInfo {
     path: string;
 }
 
+// Payload of the "download-progress" event; total and etaSeconds are null when the
+// server does not send a Content-Length
+export interface DownloadProgress {
+    downloaded: number;
+    total: number | null;
+    bytesPerSecond: number;
+    etaSeconds: number | null;
+}
+
+export interface UpdateAsset {
+    name: string;
+    url: string;
+    size: number | null;
+    sha256: string | null;
+    signature: string | null;
+}
+
+export interface UpdateInfo {
+    currentVersion: string;
+    available: boolean;
+    version: string | null;
+    channel: string;
+    notes: string | null;
+    publishedAt: string | null;
+    required: boolean;
+    minimumVersion: string | null;
+    asset: UpdateAsset | null;
+}
+
+// version is what the requested spec resolved to, e.g. "lts/*" -> "v20.19.0"
+export interface InstallResult {
+    version: string;
+    message: string;
+}
+
+// A process still running on a Node version that uninstall_node was asked to remove
+export interface BlockingProcess {
+    id: string;
+    path: string;
+}
+
+// uninstalled is false when the version is still in use and force was not set
+export interface UninstallResult {
+    uninstalled: boolean;
+    message: string;
+    projects: string[];
+    processes: BlockingProcess[];
+}
+
+// How the update started before the last exit went, reported once after relaunch
+export interface UpdateReport {
+    fromVersion: string;
+    version: string;
+    success: boolean;
+    message: string | null;
+}
+
+// The version the last update replaced, kept for rolling back
+export interface UpdateBackup {
+    version: string;
+    kind: string;
+    target: string;
+}
+
+// Whether node_modules matches the lockfile; state is "ok", "missing" or "stale"
+export interface InstallStatus {
+    state: 'ok' | 'missing' | 'stale';
+    packageManager: string | null;
+    lockfile: string | null;
+    reason: string | null;
+    installCommand: string | null;
+}
+
 export interface PlatformAPI {
     // NVM
     getNvmList(): Promise<NodeVersion[]>;
-    installNode(version: string): Promise<string>;
-    uninstallNode(version: string): Promise<string>;
+    installNode(version: string): Promise<InstallResult>;
+    uninstallNode(version: string, force?: boolean): Promise<UninstallResult>;
     useNode(version: string): Promise<string>;
     getSystemNodePath(): Promise<string>;
     getNodeVersion(path: string): Promise<string>;
 
     // Project
     scanProject(path: string): Promise<ProjectInfo>;
+    checkInstallStatus(path: string): Promise<InstallStatus>;
 
     // Runner
     runProjectCommand(id: string, path: string, script: string, packageManager: string, nodePath: string): Promise<void>;
     stopProjectCommand(id: string): Promise<void>;
+    installDependencies(id: string, path: string, packageManager: string, nodePath: string): Promise<void>;
 
     // System / Shell
     openInEditor(path: string, editor?: string): Promise<void>;
@@ -34,8 +109,12 @@ export interface PlatformAPI {
     writeTextFile(path: string, content: string): Promise<void>;
 
     // Updater
-    installUpdate(url: string): Promise<void>;
+    checkForUpdate(channel?: string): Promise<UpdateInfo | null>;
+    installUpdate(url: string, sha256: string, signature: string, silent?: boolean): Promise<void>;
     cancelUpdate(): Promise<void>;
+    takeUpdateReport(): Promise<UpdateReport | null>;
+    getUpdateBackup(): Promise<UpdateBackup | null>;
+    rollbackUpdate(): Promise<void>;
     getAppVersion(): Promise<string>;
 
     // Dialogs
@@ -53,8 +132,8 @@ export interface PlatformAPI {
 
     // Events
     onProjectOutput(callback: (payload: { id: string; data: string }) => void): Promise<() => void>;
-    onProjectExit(callback: (payload: { id: string }) => void): Promise<() => void>;
-    onDownloadProgress(callback: (percentage: number) => void): Promise<() => void>;
+    onProjectExit(callback: (payload: { id: string; success?: boolean }) => void): Promise<() => void>;
+    onDownloadProgress(callback: (progress: DownloadProgress) => void): Promise<() => void>;
 
     // Window
     windowMinimize(): Promise<void>;
@@ -55,6 +55,10 @@ export default {
     installNode: 'Install Node Version',
     uninstall: 'Uninstall',
     uninstallConfirm: 'Are you sure you want to uninstall Node {version}?',
+    inUse: 'Node {version} is still in use.',
+    inUseProjects: 'Projects: {projects}',
+    inUseProcesses: 'Running scripts (will be stopped): {processes}',
+    forceUninstall: 'Uninstall Anyway',
     setPath: 'Set Path',
     setSystemPath: 'Set System Node'
   },
@@ -88,6 +92,13 @@ export default {
     update: 'Update Settings',
     autoUpdate: 'Auto Check Updates',
     autoUpdateHint: 'Automatically check for updates on startup.',
+    updateChannel: 'Update Channel',
+    updateChannelHint: 'Beta releases arrive earlier and may be less stable.',
+    channelStable: 'Stable',
+    channelBeta: 'Beta',
+    silentUpdate: 'Silent Install',
+    silentUpdateHint: 'Install updates without the installer wizard and restart the app afterwards.',
+    rollback: 'Previous Version',
     releases: 'Release History',
     version: 'Current Version'
   },
@@ -101,16 +112,26 @@ export default {
     scripts: 'Scripts',
     addScript: 'Add Script',
     selectFolder: 'Select Folder',
-    editProject: 'Edit Project'
+    editProject: 'Edit Project',
+    installTitle: 'Install Dependencies?',
+    installNeeded: 'Dependencies need installing ({reason}). Install them before running?',
+    installAndRun: 'Install & Run',
+    runAnyway: 'Run Anyway'
   },
   update: {
     title: 'New Version Found',
     message: 'New version found: {version}. Do you want to download and install now?',
+    requiredTitle: 'Update Required',
+    requiredMessage: 'This version is no longer supported. Please install {version} to continue (minimum supported: {minimum}).',
     confirm: 'Update Now',
     cancel: 'Later',
     downloading: 'Downloading update...',
+    timeLeft: '{time} left',
     background: 'Background',
     cancelled: 'Update cancelled',
+    installed: 'Updated to {version}',
+    failed: 'The last update did not complete: {error}',
+    rollback: 'Roll back to {version}',
     error: 'Update failed: {error}'
   }
 }
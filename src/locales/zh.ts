@@ -55,6 +55,10 @@ export default {
     installNode: '安装 Node 版本',
     uninstall: '卸载',
     uninstallConfirm: '确定要卸载 Node {version} 吗？',
+    inUse: 'Node {version} 仍在使用中。',
+    inUseProjects: '使用该版本的项目：{projects}',
+    inUseProcesses: '正在运行的脚本（将被停止）：{processes}',
+    forceUninstall: '仍然卸载',
     setPath: '设置路径',
     setSystemPath: '设置默认版本'
   },
@@ -88,6 +92,13 @@ export default {
     update: '更新设置',
     autoUpdate: '自动检查更新',
     autoUpdateHint: '应用启动时自动检查新版本。',
+    updateChannel: '更新通道',
+    updateChannelHint: 'Beta 版本更早发布，但可能不够稳定。',
+    channelStable: '稳定版',
+    channelBeta: 'Beta 版',
+    silentUpdate: '静默安装',
+    silentUpdateHint: '安装更新时不显示安装向导，完成后自动重启应用。',
+    rollback: '上一版本',
     releases: '版本历史',
     version: '当前版本'
   },
@@ -101,16 +112,26 @@ export default {
     scripts: '脚本',
     addScript: '添加脚本',
     selectFolder: '选择文件夹',
-    editProject: '编辑项目'
+    editProject: '编辑项目',
+    installTitle: '安装依赖？',
+    installNeeded: '依赖需要安装（{reason}）。是否先安装再运行？',
+    installAndRun: '安装并运行',
+    runAnyway: '直接运行'
   },
   update: {
     title: '发现新版本',
     message: '发现新版本：{version}。是否立即下载并安装？',
+    requiredTitle: '需要更新',
+    requiredMessage: '当前版本已不再受支持，请安装 {version} 后继续使用（最低支持版本：{minimum}）。',
     confirm: '立即更新',
     cancel: '稍后',
     downloading: '正在下载更新...',
+    timeLeft: '剩余 {time}',
     background: '后台下载',
     cancelled: '更新已取消',
+    installed: '已更新到 {version}',
+    failed: '上次更新未完成：{error}',
+    rollback: '回滚到 {version}',
     error: '更新失败：{error}'
   }
 }
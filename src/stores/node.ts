@@ -24,9 +24,18 @@ export const useNodeStore = defineStore('node', () => {
     try {
       loading.value = true;
       const nvmNodes = await api.getNvmList();
-      // Filter out existing nvm nodes to avoid duplicates if re-fetching
-      versions.value = versions.value.filter(v => v.source !== 'nvm');
-      versions.value.push(...nvmNodes);
+      // Replace everything the backend reported last time; custom nodes and the system
+      // default belong to the store. A discovered install at the system default's path is
+      // that same node, so it fills in the version instead of being listed twice
+      const systemDefault = versions.value.find(v => v.systemDefault);
+      versions.value = versions.value.filter(v => !isDiscovered(v));
+      for (const node of nvmNodes) {
+        if (systemDefault && node.source === 'system' && samePath(node.path, systemDefault.path)) {
+          systemDefault.version = node.version;
+        } else {
+          versions.value.push(node);
+        }
+      }
 
       // Sort: System -> NVM -> Custom, then by version desc
       sortVersions();
@@ -37,11 +46,18 @@ export const useNodeStore = defineStore('node', () => {
     }
   };
 
+  const isDiscovered = (v: NodeVersion) => v.source !== 'custom' && !v.systemDefault;
+
+  const samePath = (a: string, b: string) => {
+    const normalize = (p: string) => p.replace(/[\\/]+$/, '').toLowerCase();
+    return normalize(a) === normalize(b);
+  };
+
   const sortVersions = () => {
     versions.value.sort((a, b) => {
-      // Prioritize system
-      if (a.source === 'system') return -1;
-      if (b.source === 'system') return 1;
+      // Prioritize the system default, then other system installs
+      if (!!a.systemDefault !== !!b.systemDefault) return a.systemDefault ? -1 : 1;
+      if ((a.source === 'system') !== (b.source === 'system')) return a.source === 'system' ? -1 : 1;
 
       // Then version descending
       const parse = (v: string) => v.replace(/^v/, '').split('.').map(Number);
@@ -67,7 +83,7 @@ export const useNodeStore = defineStore('node', () => {
   };
 
   const updateSystemNode = async (newPath: string) => {
-    const idx = versions.value.findIndex(v => v.source === 'system');
+    const idx = versions.value.findIndex(v => v.systemDefault);
     if (idx !== -1) {
       versions.value[idx].path = newPath;
       localStorage.setItem('system_node_path', newPath);
@@ -101,17 +117,17 @@ export const useNodeStore = defineStore('node', () => {
   const installNode = async (version: string) => {
     try {
       loading.value = true;
-      await api.installNode(version);
+      const result = await api.installNode(version);
       // After install attempt, reload list to see if it actually appeared
       await loadNvmNodes();
       
-      // Check if version exists now
+      // Check if the version the spec resolved to ("18" -> "v18.20.2") exists now
       // Normalize version string to ensure consistent comparison (e.g. "v18.0.0" vs "18.0.0")
       const normalize = (v: string) => v.toLowerCase().startsWith('v') ? v.toLowerCase() : 'v' + v.toLowerCase();
-      const targetVersion = normalize(version);
+      const targetVersion = normalize(result.version);
       
       const exists = versions.value.some(v => 
-        v.source === 'nvm' && normalize(v.version) === targetVersion
+        isDiscovered(v) && normalize(v.version) === targetVersion
       );
 
       if (!exists) {
@@ -132,10 +148,15 @@ export const useNodeStore = defineStore('node', () => {
     }
   };
 
-  const uninstallNode = async (version: string) => {
+  // Resolves with the backend's result; a version still in use is left alone (uninstalled
+  // false) unless force is set
+  const uninstallNode = async (version: string, force = false) => {
     try {
       loading.value = true;
-      await api.uninstallNode(version);
+      const result = await api.uninstallNode(version, force);
+      if (!result.uninstalled) {
+        return result;
+      }
 
       // Verification logic for uninstall
       await loadNvmNodes();
@@ -143,12 +164,12 @@ export const useNodeStore = defineStore('node', () => {
       const normalize = (v: string) => v.toLowerCase().startsWith('v') ? v.toLowerCase() : 'v' + v.toLowerCase();
       const targetVersion = normalize(version);
       
-      const exists = versions.value.some(v => v.source === 'nvm' && normalize(v.version) === targetVersion);
+      const exists = versions.value.some(v => isDiscovered(v) && normalize(v.version) === targetVersion);
 
       if (exists) {
         throw new Error('Node version still exists after uninstallation. Please check the console window for errors.');
       }
-      return true;
+      return result;
     } catch (e) {
       console.error('Failed to uninstall node', e);
       throw e;
@@ -159,7 +180,7 @@ export const useNodeStore = defineStore('node', () => {
 
   onMounted(async () => {
     // Add default system node placeholder
-    if (!versions.value.some(v => v.source === 'system')) {
+    if (!versions.value.some(v => v.systemDefault)) {
       let savedPath = localStorage.getItem('system_node_path');
 
       // If no saved path, try to resolve it automatically
@@ -190,7 +211,8 @@ export const useNodeStore = defineStore('node', () => {
       versions.value.push({
         version,
         path: savedPath!,
-        source: 'system'
+        source: 'system',
+        systemDefault: true
       });
     }
     loadCustomNodes();
@@ -1,6 +1,8 @@
 import { defineStore } from 'pinia';
 import { ref } from 'vue';
+import { ElMessageBox } from 'element-plus';
 import { api } from '../api';
+import i18n from '../i18n';
 import type { Project } from '../types';
 import { useNodeStore } from './node';
 
@@ -11,6 +13,9 @@ export const useProjectStore = defineStore('project', () => {
   const activeProjectId = ref<string | null>(null);
 
   // Load from local storage removed in favor of persistence.ts
+
+  // Runs being waited on to finish, see waitForExit
+  const exitWaiters: Record<string, (success: boolean) => void> = {};
   
   // Log buffering mechanism to optimize rendering performance
   const logBuffer: Record<string, string[]> = {};
@@ -46,7 +51,7 @@ export const useProjectStore = defineStore('project', () => {
       }
   });
 
-  api.onProjectExit(({ id }) => {
+  api.onProjectExit(({ id, success }) => {
       runningStatus.value[id] = false;
       // Ensure any buffered logs are flushed first
       if (logBuffer[id] && logBuffer[id].length > 0) {
@@ -56,8 +61,44 @@ export const useProjectStore = defineStore('project', () => {
       }
       if (!logs.value[id]) logs.value[id] = [];
       logs.value[id].push('[Process exited]');
+
+      const waiter = exitWaiters[id];
+      if (waiter) {
+        delete exitWaiters[id];
+        waiter(success !== false);
+      }
   });
 
+  function waitForExit(runId: string): Promise<boolean> {
+    return new Promise(resolve => {
+      exitWaiters[runId] = resolve;
+    });
+  }
+
+  // Before a run, offers to install when node_modules is missing or older than the lockfile.
+  // Resolves to 'install', 'run' to run as is, or 'cancel' when the dialog was closed
+  async function confirmInstall(project: Project): Promise<'install' | 'run' | 'cancel'> {
+    const status = await api.checkInstallStatus(project.path).catch(() => null);
+    if (!status || status.state === 'ok') return 'run';
+
+    const { t } = i18n.global;
+    try {
+      await ElMessageBox.confirm(
+        t('project.installNeeded', { reason: status.reason || status.state }),
+        t('project.installTitle'),
+        {
+          confirmButtonText: t('project.installAndRun'),
+          cancelButtonText: t('project.runAnyway'),
+          distinguishCancelAndClose: true,
+          type: 'warning',
+        }
+      );
+      return 'install';
+    } catch (action) {
+      return action === 'cancel' ? 'run' : 'cancel';
+    }
+  }
+
   function addProject(project: Project) {
     projects.value.push(project);
   }
@@ -96,13 +137,16 @@ export const useProjectStore = defineStore('project', () => {
             nodePath = node.path;
         } else if (project.nodeVersion === '默认' || project.nodeVersion === 'Default') {
              // System default
-             const systemNode = nodeStore.versions.find(v => v.source === 'system');
+             const systemNode = nodeStore.versions.find(v => v.systemDefault);
              if (systemNode) nodePath = systemNode.path;
         }
     }
     
     if (nodePath === 'System Default') nodePath = '';
 
+    const install = await confirmInstall(project);
+    if (install === 'cancel') return;
+
     try {
         // Initialize logs for this runId if needed, or clear if we want fresh logs per run
         // But maybe user wants to see history?
@@ -116,6 +160,18 @@ export const useProjectStore = defineStore('project', () => {
         logs.value[runId].push(`[Runner] Starting script: ${script}`);
         logs.value[runId].push(`[Runner] Selected Node Version: ${project.nodeVersion || 'None'}`);
         logs.value[runId].push(`[Runner] Resolved Node Path: ${nodePath || 'System Default'}`);
+
+        // The install runs under the same id, so its output lands in the script's console
+        if (install === 'install') {
+            logs.value[runId].push(`[Runner] Installing dependencies with ${project.packageManager}`);
+            const exited = waitForExit(runId);
+            await api.installDependencies(runId, project.path, project.packageManager, nodePath);
+            if (!(await exited)) {
+                logs.value[runId].push('[Runner] Install failed, not starting the script');
+                return;
+            }
+            runningStatus.value[runId] = true;
+        }
         
         await api.runProjectCommand(
             runId,
@@ -126,6 +182,7 @@ export const useProjectStore = defineStore('project', () => {
         );
     } catch (e) {
         console.error(e);
+        delete exitWaiters[runId];
         runningStatus.value[runId] = false;
         logs.value[runId].push(`Error starting project: ${e}`);
     }
@@ -9,7 +9,9 @@ export const useSettingsStore = defineStore('settings', () => {
     defaultTerminal: 'cmd',
     locale: 'zh',
     themeMode: 'auto',
-    autoUpdate: true
+    autoUpdate: true,
+    updateChannel: 'stable',
+    silentUpdate: false
   });
 
   const stored = localStorage.getItem('settings');
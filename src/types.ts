@@ -14,10 +14,16 @@ export interface Settings {
   locale: 'zh' | 'en';
   themeMode: 'dark' | 'light' | 'auto';
   autoUpdate: boolean;
+  updateChannel: 'stable' | 'beta';
+  // Run the installer without its wizard and restart the app afterwards
+  silentUpdate: boolean;
 }
 
 export interface NodeVersion {
   version: string;
   path: string;
-  source: 'nvm' | 'custom' | 'system';
+  source: 'nvm' | 'managed' | 'fnm' | 'volta' | 'asdf' | 'n' | 'nvs' | 'custom' | 'system';
+  // Marks the store's own system entry, the node on PATH; other 'system' entries are
+  // installs found on disk, e.g. Homebrew kegs
+  systemDefault?: boolean;
 }
@@ -102,7 +102,9 @@ window.services = {
         }
     },
 
+    // nvm resolves the spec itself here, so the requested version is reported back as-is
     installNode: async (version) => {
+        const installed = (message) => ({ version, message });
         return new Promise((resolve, reject) => {
             if (process.platform === 'win32') {
                 // Use PowerShell to start a new elevated window that runs nvm install
@@ -120,12 +122,12 @@ window.services = {
                     if (nvmHome) {
                         const versionPath = path.join(nvmHome, version);
                         if (fs.existsSync(versionPath)) {
-                            resolve("Success");
+                            resolve(installed("Success"));
                         } else {
                             reject(new Error("Installation failed or cancelled"));
                         }
                     } else {
-                        resolve("Done (Verification skipped)");
+                        resolve(installed("Done (Verification skipped)"));
                     }
                 });
             } else if (process.platform === 'darwin') {
@@ -134,7 +136,7 @@ window.services = {
                 const appleScript = `tell application "Terminal" to do script "${script}"`;
                 exec(`osascript -e '${appleScript}'`, (error) => {
                     if (error) reject(error);
-                    else resolve("Started in Terminal");
+                    else resolve(installed("Started in Terminal"));
                 });
             } else {
                 // Linux: Try common terminal emulators or fallback to background
@@ -157,19 +159,21 @@ window.services = {
                 }
 
                 if (started) {
-                    resolve("Started in Terminal");
+                    resolve(installed("Started in Terminal"));
                 } else {
                     // Fallback: run in background and capture output
                     exec(`bash -c "source ~/.nvm/nvm.sh && nvm install ${version}"`, (error, stdout, stderr) => {
                          if (error) reject(new Error(stderr || error.message));
-                         else resolve("Success");
+                         else resolve(installed("Success"));
                     });
                 }
             }
         });
     },
     
+    // uTools does not track projects or runs, so nothing ever blocks the uninstall
     uninstallNode: async (version) => {
+        const uninstalled = (message) => ({ uninstalled: true, message, projects: [], processes: [] });
         return new Promise((resolve, reject) => {
             if (process.platform === 'win32') {
                 const psCommand = `Start-Process cmd -ArgumentList '/c nvm uninstall ${version} & pause' -Verb RunAs -Wait`;
@@ -184,12 +188,12 @@ window.services = {
                     if (nvmHome) {
                         const versionPath = path.join(nvmHome, version);
                         if (!fs.existsSync(versionPath)) {
-                            resolve("Success");
+                            resolve(uninstalled("Success"));
                         } else {
                             reject(new Error("Uninstallation failed or cancelled"));
                         }
                     } else {
-                        resolve("Done");
+                        resolve(uninstalled("Done"));
                     }
                 });
             } else if (process.platform === 'darwin') {
@@ -197,13 +201,13 @@ window.services = {
                 const appleScript = `tell application "Terminal" to do script "${script}"`;
                 exec(`osascript -e '${appleScript}'`, (error) => {
                     if (error) reject(error);
-                    else resolve("Started in Terminal");
+                    else resolve(uninstalled("Started in Terminal"));
                 });
             } else {
                 // Linux
                  exec(`bash -c "source ~/.nvm/nvm.sh && nvm uninstall ${version}"`, (error, stdout, stderr) => {
                      if (error) reject(new Error(stderr || error.message));
-                     else resolve("Success");
+                     else resolve(uninstalled("Success"));
                  });
             }
         });